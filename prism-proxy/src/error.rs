@@ -0,0 +1,83 @@
+//! Typed error taxonomy for pattern lifecycle communication.
+//!
+//! The crate funnels most fallible operations through the anyhow-backed
+//! [`crate::Result`], which is fine for glue code. Pattern lifecycle calls,
+//! however, need their failures distinguished: a connection-refused is a
+//! transport problem that warrants reconnect/restart, whereas a pattern that
+//! replied "start failed" is an application-level rejection that should not be
+//! retried blindly. [`PatternError`] separates those cases so callers such as
+//! the pattern supervisor can branch on the variant.
+
+use std::fmt;
+
+/// The phase of a lifecycle interaction, used to label rejections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecyclePhase {
+    Initialize,
+    Start,
+    Drain,
+    Stop,
+    HealthCheck,
+}
+
+impl fmt::Display for LifecyclePhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LifecyclePhase::Initialize => "initialize",
+            LifecyclePhase::Start => "start",
+            LifecyclePhase::Drain => "drain",
+            LifecyclePhase::Stop => "stop",
+            LifecyclePhase::HealthCheck => "health_check",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Errors arising from pattern lifecycle communication.
+#[derive(Debug)]
+pub enum PatternError {
+    /// The channel could not be established or dropped mid-call (e.g. the
+    /// pattern process is down). Worth reconnecting for.
+    Transport(String),
+    /// The peer returned a gRPC error status that is not a clean transport
+    /// failure or deadline.
+    Rpc { code: tonic::Code, message: String },
+    /// The call completed but the pattern rejected it at the application level
+    /// (`success == false`).
+    LifecycleRejected { phase: LifecyclePhase, reason: String },
+    /// The client- or server-side deadline fired before a response arrived.
+    DeadlineExceeded { phase: LifecyclePhase },
+}
+
+impl PatternError {
+    /// Whether this error reflects a transport-level problem, as opposed to an
+    /// application-level rejection. Used to decide whether reconnecting helps.
+    pub fn is_transport(&self) -> bool {
+        matches!(self, PatternError::Transport(_))
+    }
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternError::Transport(msg) => write!(f, "transport error: {msg}"),
+            PatternError::Rpc { code, message } => {
+                write!(f, "rpc error ({code:?}): {message}")
+            }
+            PatternError::LifecycleRejected { phase, reason } => {
+                write!(f, "{phase} rejected by pattern: {reason}")
+            }
+            PatternError::DeadlineExceeded { phase } => {
+                write!(f, "{phase} deadline exceeded")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+impl From<tonic::transport::Error> for PatternError {
+    fn from(err: tonic::transport::Error) -> Self {
+        PatternError::Transport(err.to_string())
+    }
+}
@@ -7,19 +7,26 @@
 //! - Configuration management
 
 pub mod config;
+pub mod error;
 pub mod pattern;
 pub mod proto;
 pub mod router;
 pub mod server;
+pub mod shutdown;
 
 // Re-export commonly used types
 pub use config::ProxyConfig;
+pub use error::{LifecyclePhase, PatternError};
 pub use pattern::{Pattern, PatternManager, PatternStatus};
 pub use router::Router;
 pub use server::ProxyServer;
 
-/// Result type used throughout the proxy
-pub type Result<T> = anyhow::Result<T>;
+/// Result type used throughout the proxy.
+///
+/// The error parameter defaults to [`anyhow::Error`] for glue code, but is left
+/// open so lifecycle-facing APIs can surface the typed [`PatternError`] (e.g.
+/// `Result<T, PatternError>`) and let callers branch on the failure kind.
+pub type Result<T, E = anyhow::Error> = std::result::Result<T, E>;
 
 #[cfg(test)]
 mod tests {
@@ -4,28 +4,252 @@ use crate::proto::interfaces::{
     lifecycle_interface_client::LifecycleInterfaceClient, DrainRequest, HealthCheckRequest,
     InitializeRequest, StartRequest, StopRequest,
 };
+use crate::error::{LifecyclePhase, PatternError};
+use prost_types::value::Kind;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::sleep;
 use tonic::transport::Channel;
 
-/// Convert serde_json::Value to prost_types::Struct
-/// TODO: Implement proper JSON to protobuf Struct conversion
-/// For now, return an empty struct as this is not critical for POC
-fn json_value_to_prost_struct(_value: serde_json::Value) -> crate::Result<prost_types::Struct> {
-    // Return empty struct for now (prost uses BTreeMap)
-    Ok(prost_types::Struct {
-        fields: std::collections::BTreeMap::new(),
-    })
+/// Convert a [`serde_json::Value`] into a protobuf [`prost_types::Struct`].
+///
+/// Only JSON objects map cleanly onto a `Struct`; any other top-level value is
+/// rejected so configuration with an unexpected shape fails loudly rather than
+/// silently dropping fields.
+fn json_value_to_prost_struct(value: serde_json::Value) -> crate::Result<prost_types::Struct> {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut fields = BTreeMap::new();
+            for (key, val) in map {
+                fields.insert(key, json_value_to_prost_value(val)?);
+            }
+            Ok(prost_types::Struct { fields })
+        }
+        other => anyhow::bail!("expected a JSON object for pattern config, got {other}"),
+    }
+}
+
+/// Recursively convert a [`serde_json::Value`] into a [`prost_types::Value`].
+fn json_value_to_prost_value(value: serde_json::Value) -> crate::Result<prost_types::Value> {
+    let kind = match value {
+        serde_json::Value::Null => Kind::NullValue(0),
+        serde_json::Value::Bool(b) => Kind::BoolValue(b),
+        serde_json::Value::Number(n) => {
+            let f = n
+                .as_f64()
+                .ok_or_else(|| anyhow::anyhow!("number {n} is not representable as f64"))?;
+            if !f.is_finite() {
+                anyhow::bail!("non-finite number {f} cannot be encoded in a protobuf Value");
+            }
+            Kind::NumberValue(f)
+        }
+        serde_json::Value::String(s) => Kind::StringValue(s),
+        serde_json::Value::Array(items) => {
+            let values = items
+                .into_iter()
+                .map(json_value_to_prost_value)
+                .collect::<crate::Result<Vec<_>>>()?;
+            Kind::ListValue(prost_types::ListValue { values })
+        }
+        serde_json::Value::Object(_) => {
+            Kind::StructValue(json_value_to_prost_struct(value)?)
+        }
+    };
+    Ok(prost_types::Value { kind: Some(kind) })
+}
+
+/// Convert a [`prost_types::Struct`] back into a [`serde_json::Value`] object so
+/// structured payloads on `PatternMetadata` and health responses can be
+/// surfaced to callers.
+pub fn prost_struct_to_json_value(s: prost_types::Struct) -> serde_json::Value {
+    let map = s
+        .fields
+        .into_iter()
+        .map(|(k, v)| (k, prost_value_to_json_value(v)))
+        .collect();
+    serde_json::Value::Object(map)
+}
+
+/// Recursively convert a [`prost_types::Value`] back into a [`serde_json::Value`].
+fn prost_value_to_json_value(value: prost_types::Value) -> serde_json::Value {
+    match value.kind {
+        None | Some(Kind::NullValue(_)) => serde_json::Value::Null,
+        Some(Kind::BoolValue(b)) => serde_json::Value::Bool(b),
+        Some(Kind::NumberValue(f)) => number_to_json(f),
+        Some(Kind::StringValue(s)) => serde_json::Value::String(s),
+        Some(Kind::ListValue(list)) => serde_json::Value::Array(
+            list.values
+                .into_iter()
+                .map(prost_value_to_json_value)
+                .collect(),
+        ),
+        Some(Kind::StructValue(s)) => prost_struct_to_json_value(s),
+    }
+}
+
+/// Render an `f64` as JSON, preferring an integer representation for integral
+/// values so a `json -> struct -> json` round-trip is identity for integers.
+fn number_to_json(f: f64) -> serde_json::Value {
+    if f.fract() == 0.0 && f >= i64::MIN as f64 && f <= i64::MAX as f64 {
+        serde_json::Value::Number((f as i64).into())
+    } else {
+        serde_json::Number::from_f64(f)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null)
+    }
 }
 
 /// Pattern gRPC client wrapper
 pub struct PatternClient {
-    client: LifecycleInterfaceClient<Channel>,
+    /// Endpoint used to (re-)establish the channel.
+    endpoint: String,
+    /// Retry / reconnection knobs.
+    config: PatternClientConfig,
+    /// Shared lifecycle client; swapped out when the channel is re-dialed so a
+    /// background connectivity task and inline calls observe the same channel.
+    client: Arc<Mutex<LifecycleInterfaceClient<Channel>>>,
+    /// Compression / protocol settings agreed with the peer, recorded for
+    /// observability.
+    negotiated: NegotiatedSettings,
 }
 
+/// Wire-format compression algorithms negotiable with a pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// No compression.
+    None,
+    /// gzip (RFC 1952).
+    Gzip,
+    /// zstd (RFC 8878).
+    Zstd,
+}
+
+impl Compression {
+    /// Map to tonic's wire encoding, or `None` for [`Compression::None`].
+    fn encoding(self) -> Option<tonic::codec::CompressionEncoding> {
+        match self {
+            Compression::None => None,
+            Compression::Gzip => Some(tonic::codec::CompressionEncoding::Gzip),
+            Compression::Zstd => Some(tonic::codec::CompressionEncoding::Zstd),
+        }
+    }
+}
+
+/// Settings in effect after [`PatternClient::connect_with_config`], recorded on
+/// the client so operators can see which wire format is in use. Compression and
+/// protocol version are taken from the client config; the peer's own supported
+/// algorithms and version are not queried.
+#[derive(Debug, Clone)]
+pub struct NegotiatedSettings {
+    /// Compression applied to outbound (request) messages.
+    pub send: Compression,
+    /// Compression algorithms advertised as acceptable for responses.
+    pub accept: Vec<Compression>,
+    /// Protocol version this client advertises (the configured value).
+    pub protocol_version: u32,
+    /// Whether a handshake actually ran (vs. configured defaults being assumed).
+    pub handshake_performed: bool,
+}
+
+/// Tunable knobs for [`PatternClient`] reconnection behaviour.
+#[derive(Debug, Clone)]
+pub struct PatternClientConfig {
+    /// Maximum retry attempts per call before giving up.
+    pub max_retries: u32,
+    /// Initial backoff between retries.
+    pub base_backoff: Duration,
+    /// Upper bound on the backoff between retries.
+    pub max_backoff: Duration,
+    /// Interval at which the connectivity supervisor pings `health_check`.
+    pub reconnect_interval: Duration,
+    /// Per-call deadline applied to every lifecycle RPC via the `grpc-timeout`
+    /// header. `None` leaves calls without a client deadline.
+    pub call_timeout: Option<Duration>,
+    /// Compression applied to outbound request messages.
+    pub send_compression: Compression,
+    /// Compression algorithms advertised as acceptable for responses, in
+    /// preference order.
+    pub accept_compression: Vec<Compression>,
+    /// Protocol version this client advertises during the handshake.
+    pub protocol_version: u32,
+    /// Run a lightweight pre-RPC handshake to confirm the peer before the first
+    /// lifecycle call. Falls back to configured defaults when unsupported.
+    pub handshake: bool,
+}
+
+impl Default for PatternClientConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            base_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(30),
+            reconnect_interval: Duration::from_secs(10),
+            call_timeout: Some(Duration::from_secs(30)),
+            send_compression: Compression::None,
+            accept_compression: vec![Compression::Gzip, Compression::Zstd],
+            protocol_version: PROTOCOL_VERSION,
+            handshake: false,
+        }
+    }
+}
+
+/// Lifecycle wire-protocol version advertised by this client.
+pub const PROTOCOL_VERSION: u32 = 1;
+
 impl PatternClient {
-    /// Connect to a pattern's gRPC endpoint
+    /// Connect to a pattern's gRPC endpoint with default reconnection config.
     pub async fn connect(endpoint: String) -> crate::Result<Self> {
-        let client = LifecycleInterfaceClient::connect(endpoint).await?;
-        Ok(Self { client })
+        Self::connect_with_config(endpoint, PatternClientConfig::default()).await
+    }
+
+    /// Connect to a pattern's gRPC endpoint with explicit reconnection config.
+    ///
+    /// Applies the configured send/accept compression to the channel and, when
+    /// `config.handshake` is set, runs a best-effort pre-RPC handshake to
+    /// confirm the peer before the first lifecycle call. When the peer does not
+    /// support the handshake the client falls back to the configured defaults.
+    pub async fn connect_with_config(
+        endpoint: String,
+        config: PatternClientConfig,
+    ) -> crate::Result<Self> {
+        let mut client = dial(&endpoint, &config).await?;
+
+        let mut negotiated = NegotiatedSettings {
+            send: config.send_compression,
+            accept: config.accept_compression.clone(),
+            protocol_version: config.protocol_version,
+            handshake_performed: false,
+        };
+
+        // Optional handshake: the lifecycle interface exposes no dedicated
+        // capability RPC, so we probe with a cheap health check. A reachable
+        // peer confirms the configured wire format; an unreachable one leaves
+        // the defaults in place and is retried lazily on the first real call.
+        if config.handshake {
+            match client.health_check(with_timeout(HealthCheckRequest {}, config.call_timeout)).await
+            {
+                Ok(_) => negotiated.handshake_performed = true,
+                Err(status) => tracing::debug!(
+                    %endpoint,
+                    code = ?status.code(),
+                    "handshake probe failed; falling back to configured defaults"
+                ),
+            }
+        }
+
+        Ok(Self {
+            endpoint,
+            config,
+            client: Arc::new(Mutex::new(client)),
+            negotiated,
+        })
+    }
+
+    /// The compression / protocol settings agreed with the peer.
+    pub fn negotiated(&self) -> &NegotiatedSettings {
+        &self.negotiated
     }
 
     /// Initialize the pattern
@@ -35,90 +259,551 @@ impl PatternClient {
         name: String,
         version: String,
         config: serde_json::Value,
-    ) -> crate::Result<Option<crate::proto::interfaces::PatternMetadata>> {
+    ) -> crate::Result<Option<crate::proto::interfaces::PatternMetadata>, PatternError> {
         // Convert serde_json::Value to prost_types::Struct
-        let config_struct = json_value_to_prost_struct(config)?;
-
-        let request = tonic::Request::new(InitializeRequest {
+        let config_struct = json_value_to_prost_struct(config).map_err(|e| PatternError::Rpc {
+            code: tonic::Code::InvalidArgument,
+            message: e.to_string(),
+        })?;
+        let request = InitializeRequest {
             name,
             version,
             config: Some(config_struct),
-        });
+        };
 
-        let response = self.client.initialize(request).await?;
-        let init_response = response.into_inner();
+        let timeout = self.config.call_timeout;
+        let resp = self
+            .call_with_retry(LifecyclePhase::Initialize, move |mut client| {
+                let request = with_timeout(request.clone(), timeout);
+                async move { client.initialize(request).await }
+            })
+            .await?;
 
-        if !init_response.success {
-            anyhow::bail!("Initialize failed: {}", init_response.error);
+        if !resp.success {
+            return Err(PatternError::LifecycleRejected {
+                phase: LifecyclePhase::Initialize,
+                reason: resp.error,
+            });
         }
-
-        Ok(init_response.metadata)
+        Ok(resp.metadata)
     }
 
     /// Start the pattern
-    pub async fn start(&mut self) -> crate::Result<String> {
-        let request = tonic::Request::new(StartRequest {});
-
-        let response = self.client.start(request).await?;
-        let start_response = response.into_inner();
+    pub async fn start(&mut self) -> crate::Result<String, PatternError> {
+        let timeout = self.config.call_timeout;
+        let resp = self
+            .call_with_retry(LifecyclePhase::Start, move |mut client| async move {
+                client.start(with_timeout(StartRequest {}, timeout)).await
+            })
+            .await?;
 
-        if !start_response.success {
-            anyhow::bail!("Start failed: {}", start_response.error);
+        if !resp.success {
+            return Err(PatternError::LifecycleRejected {
+                phase: LifecyclePhase::Start,
+                reason: resp.error,
+            });
         }
-
-        Ok(start_response.data_endpoint)
+        Ok(resp.data_endpoint)
     }
 
     /// Drain the pattern (prepare for shutdown)
-    pub async fn drain(&mut self, timeout_seconds: i32, reason: String) -> crate::Result<()> {
-        let request = tonic::Request::new(DrainRequest {
+    pub async fn drain(
+        &mut self,
+        timeout_seconds: i32,
+        reason: String,
+    ) -> crate::Result<(), PatternError> {
+        let request = DrainRequest {
             timeout_seconds,
             reason,
-        });
-
-        let response = self.client.drain(request).await?;
-        let drain_response = response.into_inner();
+        };
+        let timeout = self.config.call_timeout;
+        let resp = self
+            .call_with_retry(LifecyclePhase::Drain, move |mut client| {
+                let request = with_timeout(request.clone(), timeout);
+                async move { client.drain(request).await }
+            })
+            .await?;
 
-        if !drain_response.success {
-            anyhow::bail!("Drain failed: {}", drain_response.error);
+        if !resp.success {
+            return Err(PatternError::LifecycleRejected {
+                phase: LifecyclePhase::Drain,
+                reason: resp.error,
+            });
         }
-
         Ok(())
     }
 
     /// Stop the pattern
-    pub async fn stop(&mut self, timeout_seconds: i32) -> crate::Result<()> {
-        let request = tonic::Request::new(StopRequest { timeout_seconds });
+    pub async fn stop(&mut self, timeout_seconds: i32) -> crate::Result<(), PatternError> {
+        let timeout = self.config.call_timeout;
+        let resp = self
+            .call_with_retry(LifecyclePhase::Stop, move |mut client| async move {
+                client
+                    .stop(with_timeout(StopRequest { timeout_seconds }, timeout))
+                    .await
+            })
+            .await?;
 
-        let response = self.client.stop(request).await?;
-        let stop_response = response.into_inner();
-
-        if !stop_response.success {
-            anyhow::bail!("Stop failed: {}", stop_response.error);
+        if !resp.success {
+            return Err(PatternError::LifecycleRejected {
+                phase: LifecyclePhase::Stop,
+                reason: resp.error,
+            });
         }
-
         Ok(())
     }
 
     /// Health check the pattern
-    pub async fn health_check(&mut self) -> crate::Result<crate::pattern::PatternStatus> {
-        let request = tonic::Request::new(HealthCheckRequest {});
-
-        let response = self.client.health_check(request).await?;
-        let health_response = response.into_inner();
+    pub async fn health_check(&mut self) -> crate::Result<crate::pattern::PatternStatus, PatternError> {
+        let timeout = self.config.call_timeout;
+        let resp = self
+            .call_with_retry(LifecyclePhase::HealthCheck, move |mut client| async move {
+                client
+                    .health_check(with_timeout(HealthCheckRequest {}, timeout))
+                    .await
+            })
+            .await?;
 
         use crate::proto::interfaces::HealthStatus;
-        let status = match HealthStatus::try_from(health_response.status) {
+        let status = match HealthStatus::try_from(resp.status) {
             Ok(HealthStatus::Healthy) => crate::pattern::PatternStatus::Running,
             Ok(HealthStatus::Degraded) => crate::pattern::PatternStatus::Degraded,
-            Ok(HealthStatus::Unhealthy) => {
-                crate::pattern::PatternStatus::Failed(health_response.message)
-            }
+            Ok(HealthStatus::Unhealthy) => crate::pattern::PatternStatus::Failed(resp.message),
             _ => crate::pattern::PatternStatus::Failed("Unknown health status".to_string()),
         };
-
         Ok(status)
     }
+
+    /// Spawn a background connectivity supervisor that periodically pings
+    /// `health_check` and proactively re-establishes the channel when the peer
+    /// becomes unreachable, rather than waiting for the next user call.
+    pub fn spawn_connectivity_supervisor(&self) -> tokio::task::JoinHandle<()> {
+        let endpoint = self.endpoint.clone();
+        let config = self.config.clone();
+        let interval = self.config.reconnect_interval;
+        let timeout = self.config.call_timeout;
+        let client = self.client.clone();
+
+        tokio::spawn(async move {
+            loop {
+                sleep(interval).await;
+                let probe = {
+                    let mut guard = client.lock().await;
+                    guard.health_check(with_timeout(HealthCheckRequest {}, timeout)).await
+                };
+                if let Err(status) = probe {
+                    if is_transport_error(&status) {
+                        tracing::warn!(
+                            %endpoint,
+                            code = ?status.code(),
+                            "connectivity probe failed, re-establishing channel"
+                        );
+                        if let Ok(fresh) = dial(&endpoint, &config).await {
+                            *client.lock().await = fresh;
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Execute a lifecycle RPC, transparently re-dialing and retrying on
+    /// transport-level errors using capped exponential backoff with jitter.
+    async fn call_with_retry<T, F, Fut>(
+        &self,
+        phase: LifecyclePhase,
+        f: F,
+    ) -> crate::Result<T, PatternError>
+    where
+        F: Fn(LifecycleInterfaceClient<Channel>) -> Fut,
+        Fut: std::future::Future<Output = Result<tonic::Response<T>, tonic::Status>>,
+    {
+        let mut attempt: u32 = 0;
+        let mut backoff = self.config.base_backoff;
+
+        loop {
+            let client = self.client.lock().await.clone();
+            match f(client).await {
+                Ok(response) => return Ok(response.into_inner()),
+                Err(status) => {
+                    // A fired client deadline is reported distinctly so callers
+                    // can treat a slow peer differently from a dead one.
+                    if is_deadline(&status) {
+                        return Err(PatternError::DeadlineExceeded { phase });
+                    }
+                    if !is_transport_error(&status) {
+                        // A non-transport status: surface it as a typed RPC error
+                        // so callers can branch.
+                        return Err(PatternError::Rpc {
+                            code: status.code(),
+                            message: status.message().to_string(),
+                        });
+                    }
+                    if attempt >= self.config.max_retries {
+                        // Exhausted transport retries: the peer is unreachable.
+                        return Err(PatternError::Transport(status.message().to_string()));
+                    }
+                    attempt += 1;
+                    let delay = jittered(backoff);
+                    tracing::warn!(
+                        phase = %phase,
+                        attempt,
+                        backoff_ms = delay.as_millis(),
+                        code = ?status.code(),
+                        "lifecycle RPC transport error, reconnecting and retrying"
+                    );
+                    sleep(delay).await;
+                    backoff = (backoff * 2).min(self.config.max_backoff);
+                    if let Err(e) = self.reconnect().await {
+                        // Keep the current channel; the next attempt counts
+                        // against the retry budget like any transport failure.
+                        tracing::debug!(phase = %phase, error = %e, "re-dial failed");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Re-dial the endpoint and swap in the fresh client, re-applying the
+    /// negotiated compression settings.
+    async fn reconnect(&self) -> crate::Result<(), PatternError> {
+        let fresh = dial(&self.endpoint, &self.config).await?;
+        *self.client.lock().await = fresh;
+        Ok(())
+    }
+}
+
+/// Abstraction over a pattern's lifecycle RPCs.
+///
+/// [`PatternClient`] is the production implementation backed by a real gRPC
+/// channel; tests substitute an in-memory mock so lifecycle-dependent logic
+/// (crash detection, slow startup, drain/stop sequencing) can run fully
+/// in-process. Code written against `impl LifecycleClient` rather than the
+/// concrete client can be given either.
+#[tonic::async_trait]
+pub trait LifecycleClient: Send + Sync {
+    /// Initialize the pattern, returning its declared metadata.
+    async fn initialize(
+        &mut self,
+        name: String,
+        version: String,
+        config: serde_json::Value,
+    ) -> crate::Result<Option<crate::proto::interfaces::PatternMetadata>, PatternError>;
+
+    /// Start the pattern, returning its data-plane endpoint.
+    async fn start(&mut self) -> crate::Result<String, PatternError>;
+
+    /// Drain the pattern ahead of shutdown.
+    async fn drain(
+        &mut self,
+        timeout_seconds: i32,
+        reason: String,
+    ) -> crate::Result<(), PatternError>;
+
+    /// Stop the pattern.
+    async fn stop(&mut self, timeout_seconds: i32) -> crate::Result<(), PatternError>;
+
+    /// Report the pattern's current health.
+    async fn health_check(&mut self) -> crate::Result<crate::pattern::PatternStatus, PatternError>;
+}
+
+#[tonic::async_trait]
+impl LifecycleClient for PatternClient {
+    async fn initialize(
+        &mut self,
+        name: String,
+        version: String,
+        config: serde_json::Value,
+    ) -> crate::Result<Option<crate::proto::interfaces::PatternMetadata>, PatternError> {
+        // Inherent methods shadow the trait methods, so this delegates rather
+        // than recursing.
+        PatternClient::initialize(self, name, version, config).await
+    }
+
+    async fn start(&mut self) -> crate::Result<String, PatternError> {
+        PatternClient::start(self).await
+    }
+
+    async fn drain(
+        &mut self,
+        timeout_seconds: i32,
+        reason: String,
+    ) -> crate::Result<(), PatternError> {
+        PatternClient::drain(self, timeout_seconds, reason).await
+    }
+
+    async fn stop(&mut self, timeout_seconds: i32) -> crate::Result<(), PatternError> {
+        PatternClient::stop(self, timeout_seconds).await
+    }
+
+    async fn health_check(&mut self) -> crate::Result<crate::pattern::PatternStatus, PatternError> {
+        PatternClient::health_check(self).await
+    }
+}
+
+/// Dial the endpoint and apply the configured compression settings.
+async fn dial(
+    endpoint: &str,
+    config: &PatternClientConfig,
+) -> crate::Result<LifecycleInterfaceClient<Channel>, PatternError> {
+    let mut client = LifecycleInterfaceClient::connect(endpoint.to_string()).await?;
+    if let Some(encoding) = config.send_compression.encoding() {
+        client = client.send_compressed(encoding);
+    }
+    for accept in config.accept_compression.iter().filter_map(|c| c.encoding()) {
+        client = client.accept_compressed(accept);
+    }
+    Ok(client)
+}
+
+/// Wrap a prost message in a [`tonic::Request`], attaching the `grpc-timeout`
+/// header when a client deadline is configured.
+fn with_timeout<T>(message: T, timeout: Option<Duration>) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+    if let Some(timeout) = timeout {
+        request.set_timeout(timeout);
+    }
+    request
+}
+
+/// Whether a gRPC status reflects a transport-level failure worth reconnecting
+/// for (as opposed to an application-level rejection).
+fn is_transport_error(status: &tonic::Status) -> bool {
+    use tonic::Code;
+    matches!(status.code(), Code::Unavailable | Code::Unknown | Code::Aborted)
+}
+
+/// Whether a gRPC status reflects an expired deadline. tonic maps a client
+/// deadline that fires before the response arrives to `Cancelled`, while the
+/// server honoring `grpc-timeout` reports `DeadlineExceeded`.
+fn is_deadline(status: &tonic::Status) -> bool {
+    use tonic::Code;
+    matches!(status.code(), Code::DeadlineExceeded | Code::Cancelled)
+}
+
+/// Apply jitter to a backoff duration, scaling it to between half and all of its
+/// nominal value, using a dependency-free clock source.
+fn jittered(delay: Duration) -> Duration {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let frac = (nanos % 1_000) as f64 / 1_000.0;
+    delay.mul_f64(0.5 + 0.5 * frac)
+}
+
+/// Test-only implementations of [`LifecycleClient`] and the server side of the
+/// lifecycle interface, so lifecycle-dependent logic can be exercised fully
+/// in-process without external pattern binaries.
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::*;
+    use crate::pattern::PatternStatus;
+    use crate::proto::interfaces::lifecycle_interface_server::{
+        LifecycleInterface, LifecycleInterfaceServer,
+    };
+    use crate::proto::interfaces::{
+        DrainResponse, HealthCheckResponse, HealthStatus, InitializeResponse, StartResponse,
+        StopResponse,
+    };
+    use std::collections::VecDeque;
+    use std::sync::Mutex as StdMutex;
+    use tokio::sync::oneshot;
+    use tonic::{Request, Response, Status};
+
+    /// A scriptable, in-memory [`LifecycleClient`].
+    ///
+    /// Health-check responses are drained from `health_sequence`, defaulting to
+    /// `Running` once exhausted; `startup_delay` simulates a slow `start` and
+    /// `fail_start` a rejected one.
+    #[derive(Default)]
+    pub struct MockLifecycleClient {
+        pub started: bool,
+        pub health_sequence: VecDeque<PatternStatus>,
+        pub fail_start: Option<String>,
+        pub startup_delay: Option<Duration>,
+    }
+
+    #[tonic::async_trait]
+    impl LifecycleClient for MockLifecycleClient {
+        async fn initialize(
+            &mut self,
+            _name: String,
+            _version: String,
+            _config: serde_json::Value,
+        ) -> crate::Result<Option<crate::proto::interfaces::PatternMetadata>, PatternError> {
+            Ok(None)
+        }
+
+        async fn start(&mut self) -> crate::Result<String, PatternError> {
+            if let Some(delay) = self.startup_delay {
+                sleep(delay).await;
+            }
+            if let Some(reason) = &self.fail_start {
+                return Err(PatternError::LifecycleRejected {
+                    phase: LifecyclePhase::Start,
+                    reason: reason.clone(),
+                });
+            }
+            self.started = true;
+            Ok("127.0.0.1:0".to_string())
+        }
+
+        async fn drain(
+            &mut self,
+            _timeout_seconds: i32,
+            _reason: String,
+        ) -> crate::Result<(), PatternError> {
+            Ok(())
+        }
+
+        async fn stop(&mut self, _timeout_seconds: i32) -> crate::Result<(), PatternError> {
+            self.started = false;
+            Ok(())
+        }
+
+        async fn health_check(&mut self) -> crate::Result<PatternStatus, PatternError> {
+            Ok(self
+                .health_sequence
+                .pop_front()
+                .unwrap_or(PatternStatus::Running))
+        }
+    }
+
+    /// How a spawned [`MockServer`] should respond.
+    #[derive(Clone)]
+    pub struct MockServerConfig {
+        /// Whether `start` reports success.
+        pub start_success: bool,
+        /// Health status returned by every `health_check`.
+        pub health: HealthStatus,
+        /// Delay injected before each response, simulating a slow peer.
+        pub delay: Option<Duration>,
+    }
+
+    impl Default for MockServerConfig {
+        fn default() -> Self {
+            Self {
+                start_success: true,
+                health: HealthStatus::Healthy,
+                delay: None,
+            }
+        }
+    }
+
+    struct MockLifecycleService {
+        config: MockServerConfig,
+        start_count: StdMutex<u32>,
+    }
+
+    #[tonic::async_trait]
+    impl LifecycleInterface for MockLifecycleService {
+        async fn initialize(
+            &self,
+            _request: Request<InitializeRequest>,
+        ) -> Result<Response<InitializeResponse>, Status> {
+            self.pause().await;
+            Ok(Response::new(InitializeResponse {
+                success: true,
+                error: String::new(),
+                metadata: None,
+            }))
+        }
+
+        async fn start(
+            &self,
+            _request: Request<StartRequest>,
+        ) -> Result<Response<StartResponse>, Status> {
+            self.pause().await;
+            *self.start_count.lock().unwrap() += 1;
+            Ok(Response::new(StartResponse {
+                success: self.config.start_success,
+                error: if self.config.start_success {
+                    String::new()
+                } else {
+                    "scripted start failure".to_string()
+                },
+                data_endpoint: "127.0.0.1:0".to_string(),
+            }))
+        }
+
+        async fn drain(
+            &self,
+            _request: Request<DrainRequest>,
+        ) -> Result<Response<DrainResponse>, Status> {
+            self.pause().await;
+            Ok(Response::new(DrainResponse {
+                success: true,
+                error: String::new(),
+            }))
+        }
+
+        async fn stop(
+            &self,
+            _request: Request<StopRequest>,
+        ) -> Result<Response<StopResponse>, Status> {
+            self.pause().await;
+            Ok(Response::new(StopResponse {
+                success: true,
+                error: String::new(),
+            }))
+        }
+
+        async fn health_check(
+            &self,
+            _request: Request<HealthCheckRequest>,
+        ) -> Result<Response<HealthCheckResponse>, Status> {
+            self.pause().await;
+            Ok(Response::new(HealthCheckResponse {
+                status: self.config.health as i32,
+                message: String::new(),
+            }))
+        }
+    }
+
+    impl MockLifecycleService {
+        async fn pause(&self) {
+            if let Some(delay) = self.config.delay {
+                sleep(delay).await;
+            }
+        }
+    }
+
+    /// A running in-process lifecycle server. Dropping it shuts the server
+    /// down.
+    pub struct MockServer {
+        /// `http://host:port` endpoint a [`PatternClient`] can dial.
+        pub endpoint: String,
+        _shutdown: oneshot::Sender<()>,
+    }
+
+    /// Spawn an in-process lifecycle server on an ephemeral port.
+    pub async fn spawn(config: MockServerConfig) -> crate::Result<MockServer> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let incoming = tonic::transport::server::TcpIncoming::from_listener(listener, true, None)
+            .map_err(|e| anyhow::anyhow!("failed to wrap listener: {e}"))?;
+
+        let service = MockLifecycleService {
+            config,
+            start_count: StdMutex::new(0),
+        };
+        let (tx, rx) = oneshot::channel::<()>();
+
+        tokio::spawn(async move {
+            let _ = tonic::transport::Server::builder()
+                .add_service(LifecycleInterfaceServer::new(service))
+                .serve_with_incoming_shutdown(incoming, async {
+                    rx.await.ok();
+                })
+                .await;
+        });
+
+        Ok(MockServer {
+            endpoint: format!("http://{addr}"),
+            _shutdown: tx,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -135,6 +820,176 @@ mod tests {
         );
     }
 
-    // Note: More comprehensive tests require a mock gRPC server
-    // We'll test the full integration in integration tests
+    #[test]
+    fn test_pattern_error_classifies_transport_vs_rejection() {
+        assert!(PatternError::Transport("refused".into()).is_transport());
+        assert!(!PatternError::LifecycleRejected {
+            phase: LifecyclePhase::Start,
+            reason: "boom".into(),
+        }
+        .is_transport());
+        assert!(!PatternError::Rpc {
+            code: tonic::Code::InvalidArgument,
+            message: "bad".into(),
+        }
+        .is_transport());
+    }
+
+    #[tokio::test]
+    async fn test_mock_client_surfaces_typed_start_rejection() {
+        let mut client = mock::MockLifecycleClient {
+            fail_start: Some("no capacity".to_string()),
+            ..Default::default()
+        };
+        match LifecycleClient::start(&mut client).await {
+            Err(PatternError::LifecycleRejected { phase, reason }) => {
+                assert_eq!(phase, LifecyclePhase::Start);
+                assert_eq!(reason, "no capacity");
+            }
+            other => panic!("expected lifecycle rejection, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mock_client_scripts_health_and_start() {
+        use crate::pattern::PatternStatus;
+        let mut client = mock::MockLifecycleClient {
+            health_sequence: [PatternStatus::Degraded, PatternStatus::Running]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        assert_eq!(
+            LifecycleClient::health_check(&mut client).await.unwrap(),
+            PatternStatus::Degraded
+        );
+        assert_eq!(
+            LifecycleClient::health_check(&mut client).await.unwrap(),
+            PatternStatus::Running
+        );
+        // Exhausted sequence defaults to Running.
+        assert_eq!(
+            LifecycleClient::health_check(&mut client).await.unwrap(),
+            PatternStatus::Running
+        );
+
+        assert!(LifecycleClient::start(&mut client).await.is_ok());
+        assert!(client.started);
+    }
+
+    #[tokio::test]
+    async fn test_real_client_against_in_process_server() {
+        use crate::pattern::PatternStatus;
+        let server = mock::spawn(mock::MockServerConfig::default())
+            .await
+            .expect("spawn mock server");
+        let mut client = PatternClient::connect(server.endpoint.clone())
+            .await
+            .expect("connect to mock server");
+
+        client
+            .initialize("t".to_string(), "1".to_string(), serde_json::json!({}))
+            .await
+            .expect("initialize");
+        let endpoint = client.start().await.expect("start");
+        assert!(!endpoint.is_empty());
+        assert_eq!(
+            client.health_check().await.expect("health"),
+            PatternStatus::Running
+        );
+        client.stop(1).await.expect("stop");
+    }
+
+    fn roundtrip(value: serde_json::Value) {
+        let s = json_value_to_prost_struct(value.clone()).expect("to struct");
+        let back = prost_struct_to_json_value(s);
+        assert_eq!(back, value, "json -> struct -> json should be identity");
+    }
+
+    #[test]
+    fn test_struct_roundtrip_scalars() {
+        roundtrip(serde_json::json!({
+            "string": "hello",
+            "int": 42,
+            "float": 1.5,
+            "bool_true": true,
+            "bool_false": false,
+            "null": null
+        }));
+    }
+
+    #[test]
+    fn test_struct_roundtrip_nested() {
+        roundtrip(serde_json::json!({
+            "nested": { "a": 1, "b": ["x", "y", { "deep": true }] },
+            "list": [1, 2, 3],
+            "empty_obj": {},
+            "empty_list": []
+        }));
+    }
+
+    #[test]
+    fn test_non_object_top_level_is_rejected() {
+        assert!(json_value_to_prost_struct(serde_json::json!([1, 2, 3])).is_err());
+        assert!(json_value_to_prost_struct(serde_json::json!("scalar")).is_err());
+    }
+
+    #[test]
+    fn test_backoff_is_jittered_within_bounds() {
+        // Jitter keeps the delay within [0.5, 1.0] of the nominal value.
+        let base = Duration::from_millis(200);
+        let d = jittered(base);
+        assert!(d >= base / 2 && d <= base, "jittered delay out of bounds: {d:?}");
+    }
+
+    #[test]
+    fn test_transport_errors_are_retryable() {
+        use tonic::{Code, Status};
+        assert!(is_transport_error(&Status::new(Code::Unavailable, "down")));
+        assert!(is_transport_error(&Status::new(Code::Unknown, "?")));
+        // Application-level rejections must not trigger a reconnect.
+        assert!(!is_transport_error(&Status::new(Code::InvalidArgument, "bad")));
+        assert!(!is_transport_error(&Status::new(Code::NotFound, "missing")));
+    }
+
+    #[test]
+    fn test_deadline_statuses_are_classified() {
+        use tonic::{Code, Status};
+        assert!(is_deadline(&Status::new(Code::DeadlineExceeded, "slow")));
+        assert!(is_deadline(&Status::new(Code::Cancelled, "client deadline")));
+        assert!(!is_deadline(&Status::new(Code::Unavailable, "down")));
+    }
+
+    #[test]
+    fn test_compression_maps_to_tonic_encoding() {
+        use tonic::codec::CompressionEncoding;
+        assert!(Compression::None.encoding().is_none());
+        assert!(matches!(
+            Compression::Gzip.encoding(),
+            Some(CompressionEncoding::Gzip)
+        ));
+        assert!(matches!(
+            Compression::Zstd.encoding(),
+            Some(CompressionEncoding::Zstd)
+        ));
+    }
+
+    #[test]
+    fn test_default_config_accepts_compressed_responses() {
+        let config = PatternClientConfig::default();
+        assert_eq!(config.send_compression, Compression::None);
+        assert!(config.accept_compression.contains(&Compression::Gzip));
+        assert!(config.accept_compression.contains(&Compression::Zstd));
+        assert_eq!(config.protocol_version, PROTOCOL_VERSION);
+        assert!(!config.handshake);
+    }
+
+    #[test]
+    fn test_with_timeout_is_noop_when_unset() {
+        // A configured deadline is attached; `None` leaves the request untouched.
+        let req = with_timeout(StartRequest {}, Some(Duration::from_secs(5)));
+        assert!(req.metadata().get("grpc-timeout").is_some());
+        let req = with_timeout(StartRequest {}, None);
+        assert!(req.metadata().get("grpc-timeout").is_none());
+    }
 }
@@ -1,29 +1,192 @@
 //! Request routing for Prism proxy
+//!
+//! The router owns a namespace → pattern route table and resolves it lazily.
+//! Resolution is cached per namespace behind a concurrent map and guarded by a
+//! single in-flight resolution, so a burst of requests for an unresolved
+//! namespace only triggers one lookup. Failed resolutions are negatively cached
+//! for a short TTL and retried afterwards.
 
 use crate::pattern::PatternManager;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Notify, RwLock};
 
-/// Router - routes requests to appropriate patterns
+/// A hot-swappable handle to the live [`Router`].
+///
+/// The server swaps the inner `Arc<Router>` on configuration reload; requests
+/// that already cloned the old `Arc` keep using it until they finish.
+pub type SharedRouter = Arc<RwLock<Arc<Router>>>;
+
+/// How long a failed route resolution is cached before it is retried.
+const NEGATIVE_CACHE_TTL: Duration = Duration::from_secs(2);
+
+/// Resolution state of a namespace route.
+///
+/// Modelled on the per-topic route bookkeeping a messaging client keeps: a
+/// namespace is either being resolved, resolved to a concrete pattern, or
+/// marked failed until the negative-cache TTL elapses.
+#[derive(Debug, Clone)]
+pub enum RouteStatus {
+    /// A freshly created entry that nobody has started resolving yet. The first
+    /// task to observe it claims the `Querying` slot in the slow path.
+    Unresolved,
+    /// A resolution is currently in flight; waiters should await the notify.
+    Querying,
+    /// The namespace resolved to the named pattern.
+    Found { pattern: String },
+    /// Resolution failed at the given instant; retry once the TTL elapses.
+    Failed { at: Instant, reason: String },
+}
+
+/// Cache entry for a single namespace, carrying the current status and a notify
+/// that wakes waiters once an in-flight resolution settles.
+struct RouteEntry {
+    status: RwLock<RouteStatus>,
+    resolved: Notify,
+}
+
+/// Router - routes requests to appropriate patterns.
 pub struct Router {
-    _pattern_manager: Arc<PatternManager>,
+    pub(crate) pattern_manager: Arc<PatternManager>,
+    /// Static namespace → pattern name mapping supplied by configuration.
+    routes: RwLock<HashMap<String, String>>,
+    /// Per-namespace resolution cache.
+    cache: RwLock<HashMap<String, Arc<RouteEntry>>>,
 }
 
 impl Router {
-    /// Create a new router
+    /// Create a new router.
     pub fn new(pattern_manager: Arc<PatternManager>) -> Self {
         Self {
-            _pattern_manager: pattern_manager,
+            pattern_manager,
+            routes: RwLock::new(HashMap::new()),
+            cache: RwLock::new(HashMap::new()),
         }
     }
 
-    /// Route a request to a pattern
+    /// Register a namespace → pattern route.
+    pub async fn register_route(&self, namespace: String, pattern: String) {
+        tracing::info!(%namespace, %pattern, "registering route");
+        self.routes.write().await.insert(namespace, pattern);
+    }
+
+    /// Route a request to the pattern backing `namespace`, returning the raw
+    /// response bytes produced by that pattern.
+    ///
+    /// Missing or unhealthy patterns surface as an error whose message carries
+    /// an `unavailable` marker so callers can map it to `Status::unavailable`.
     pub async fn route_request(
         &self,
-        _namespace: &str,
-        _request: Vec<u8>,
+        namespace: &str,
+        request: Vec<u8>,
     ) -> crate::Result<Vec<u8>> {
-        // TODO: Implement request routing
-        Ok(Vec::new())
+        let pattern = self.resolve(namespace).await?;
+        self.pattern_manager.forward(&pattern, request).await
+    }
+
+    /// Resolve a namespace to the pattern that serves it, consulting the cache
+    /// first and coalescing concurrent misses into a single lookup.
+    async fn resolve(&self, namespace: &str) -> crate::Result<String> {
+        loop {
+            let entry = self.cache_entry(namespace).await;
+
+            // Fast path: a settled cache entry.
+            {
+                let status = entry.status.read().await;
+                match &*status {
+                    RouteStatus::Found { pattern } => return Ok(pattern.clone()),
+                    RouteStatus::Failed { at, reason } => {
+                        if at.elapsed() < NEGATIVE_CACHE_TTL {
+                            anyhow::bail!("unavailable: namespace {namespace}: {reason}");
+                        }
+                        // TTL elapsed: fall through and re-resolve.
+                    }
+                    RouteStatus::Querying => {
+                        // Someone else is resolving; wait and re-check.
+                        drop(status);
+                        entry.resolved.notified().await;
+                        continue;
+                    }
+                    RouteStatus::Unresolved => {
+                        // Nobody has started yet; fall through to claim the slot.
+                    }
+                }
+            }
+
+            // Slow path: claim the in-flight slot. Only the writer that observes
+            // a non-`Querying` status performs the lookup; others loop back and
+            // wait on the notify.
+            {
+                let mut status = entry.status.write().await;
+                match &*status {
+                    RouteStatus::Querying => {
+                        drop(status);
+                        entry.resolved.notified().await;
+                        continue;
+                    }
+                    RouteStatus::Found { pattern } => return Ok(pattern.clone()),
+                    RouteStatus::Failed { at, .. } if at.elapsed() < NEGATIVE_CACHE_TTL => {
+                        anyhow::bail!("unavailable: namespace {namespace}");
+                    }
+                    _ => *status = RouteStatus::Querying,
+                }
+            }
+
+            let outcome = self.lookup(namespace).await;
+            {
+                let mut status = entry.status.write().await;
+                *status = match &outcome {
+                    Ok(pattern) => RouteStatus::Found {
+                        pattern: pattern.clone(),
+                    },
+                    Err(e) => RouteStatus::Failed {
+                        at: Instant::now(),
+                        reason: e.to_string(),
+                    },
+                };
+            }
+            entry.resolved.notify_waiters();
+            return outcome;
+        }
+    }
+
+    /// Look up or create the cache entry for a namespace.
+    async fn cache_entry(&self, namespace: &str) -> Arc<RouteEntry> {
+        if let Some(entry) = self.cache.read().await.get(namespace) {
+            return entry.clone();
+        }
+        self.cache
+            .write()
+            .await
+            .entry(namespace.to_string())
+            .or_insert_with(|| {
+                Arc::new(RouteEntry {
+                    status: RwLock::new(RouteStatus::Unresolved),
+                    resolved: Notify::new(),
+                })
+            })
+            .clone()
+    }
+
+    /// Perform the actual route resolution: map the namespace to a pattern and
+    /// confirm the pattern is registered and healthy.
+    async fn lookup(&self, namespace: &str) -> crate::Result<String> {
+        let pattern = self
+            .routes
+            .read()
+            .await
+            .get(namespace)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("unavailable: no route for namespace {namespace}"))?;
+
+        match self.pattern_manager.get_pattern(&pattern).await {
+            Some((_, status, _, _)) if status.is_serving() => Ok(pattern),
+            Some((_, status, _, _)) => {
+                anyhow::bail!("unavailable: pattern {pattern} is {status:?}")
+            }
+            None => anyhow::bail!("unavailable: pattern {pattern} is not registered"),
+        }
     }
 }
 
@@ -37,4 +200,33 @@ mod tests {
         let _router = Router::new(pattern_manager);
         // Router created successfully
     }
+
+    #[tokio::test]
+    async fn test_unrouted_namespace_is_unavailable() {
+        let pattern_manager = Arc::new(PatternManager::new());
+        let router = Router::new(pattern_manager);
+
+        let err = router
+            .route_request("missing", Vec::new())
+            .await
+            .expect_err("unrouted namespace should fail");
+        assert!(err.to_string().contains("unavailable"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_resolution_is_negatively_cached() {
+        let pattern_manager = Arc::new(PatternManager::new());
+        let router = Router::new(pattern_manager);
+        router
+            .register_route("ns".to_string(), "ghost".to_string())
+            .await;
+
+        // Pattern is not registered, so resolution fails and is cached.
+        assert!(router.resolve("ns").await.is_err());
+        let entry = router.cache_entry("ns").await;
+        assert!(matches!(
+            &*entry.status.read().await,
+            RouteStatus::Failed { .. }
+        ));
+    }
 }
@@ -0,0 +1,119 @@
+//! Active-connection tracking layer.
+//!
+//! A tower [`Layer`] installed on the tonic `Server::builder()` that increments
+//! a shared counter when a request begins and decrements it — via a guard that
+//! fires on drop, even on error or cancellation — when it completes. Once the
+//! server is draining, new requests are rejected with `Status::unavailable` so
+//! the drain phase can wait on a truthful count of in-flight work before
+//! stopping patterns.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tonic::body::BoxBody;
+use tonic::Status;
+use tower::{Layer, Service};
+
+/// Shared state observed by [`ConnTrackLayer`] and the server.
+#[derive(Clone, Default)]
+pub struct ConnTracker {
+    active: Arc<AtomicUsize>,
+    draining: Arc<AtomicBool>,
+}
+
+impl ConnTracker {
+    /// Number of in-flight frontend requests.
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// Mark the tracker as draining; new requests will be rejected.
+    pub fn set_draining(&self, draining: bool) {
+        self.draining.store(draining, Ordering::Relaxed);
+    }
+
+    /// Share the underlying counter so observers (e.g. the drain loop) read the
+    /// same value the layer updates.
+    pub fn counter(&self) -> Arc<AtomicUsize> {
+        self.active.clone()
+    }
+}
+
+/// Decrements the active counter when dropped, regardless of how the request
+/// future finishes.
+struct ActiveGuard(Arc<AtomicUsize>);
+
+impl Drop for ActiveGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Tower layer that installs [`ConnTrack`].
+#[derive(Clone)]
+pub struct ConnTrackLayer {
+    tracker: ConnTracker,
+}
+
+impl ConnTrackLayer {
+    pub fn new(tracker: ConnTracker) -> Self {
+        Self { tracker }
+    }
+}
+
+impl<S> Layer<S> for ConnTrackLayer {
+    type Service = ConnTrack<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConnTrack {
+            inner,
+            tracker: self.tracker.clone(),
+        }
+    }
+}
+
+/// Service wrapper that counts in-flight requests and rejects new ones while
+/// draining.
+#[derive(Clone)]
+pub struct ConnTrack<S> {
+    inner: S,
+    tracker: ConnTracker,
+}
+
+impl<S, ReqBody> Service<http::Request<ReqBody>> for ConnTrack<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<BoxBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future =
+        std::pin::Pin<Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<ReqBody>) -> Self::Future {
+        let tracker = self.tracker.clone();
+
+        // Reject new work once draining.
+        if tracker.draining.load(Ordering::Relaxed) {
+            let response = Status::unavailable("server is draining").into_http();
+            return Box::pin(async move { Ok(response) });
+        }
+
+        // Count this request and arrange for the count to drop when it finishes.
+        tracker.active.fetch_add(1, Ordering::Relaxed);
+        let guard = ActiveGuard(tracker.active.clone());
+
+        // `Clone` the inner service so the borrow ends before the async move.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(async move {
+            let _guard = guard;
+            inner.call(req).await
+        })
+    }
+}
@@ -0,0 +1,159 @@
+//! Proxy filter middleware chain.
+//!
+//! Filters let operators inspect, rewrite, or reject KeyValue requests and
+//! responses without touching pattern binaries — key-prefix enforcement,
+//! value-size limits, PII redaction, tag-based rejection, and so on. The chain
+//! is an ordered `Vec<Arc<dyn ProxyFilter>>`; [`KeyValueService`] runs it on the
+//! way in (short-circuiting on the first [`FilterOutcome::Reject`]) and on the
+//! way out.
+//!
+//! [`KeyValueService`]: super::keyvalue::KeyValueService
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use tonic::Status;
+
+/// The KeyValue operation a request represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    Set,
+    Get,
+    Delete,
+    Exists,
+}
+
+/// Per-request context handed to every filter so it can make and trace
+/// decisions.
+#[derive(Debug, Clone)]
+pub struct FilterContext {
+    /// Namespace the request is routed to.
+    pub namespace: String,
+    /// Which KeyValue operation is being performed.
+    pub operation: OperationKind,
+    /// Correlation id, unique per request, for logging/tracing.
+    pub correlation_id: String,
+}
+
+impl FilterContext {
+    /// Build a context for a request, minting a fresh correlation id.
+    pub fn new(namespace: impl Into<String>, operation: OperationKind) -> Self {
+        Self {
+            namespace: namespace.into(),
+            operation,
+            correlation_id: next_correlation_id(),
+        }
+    }
+}
+
+/// Outcome of running a filter against a request.
+pub enum FilterOutcome {
+    /// Leave the request unchanged.
+    Pass,
+    /// Replace the request payload with the given bytes.
+    Modify(Vec<u8>),
+    /// Reject the request with the given status; the chain short-circuits.
+    Reject(Status),
+}
+
+/// A middleware filter applied to KeyValue requests and responses.
+#[tonic::async_trait]
+pub trait ProxyFilter: Send + Sync {
+    /// Inspect or rewrite an inbound request.
+    async fn on_request(&self, ctx: &FilterContext, req: Vec<u8>) -> FilterOutcome;
+
+    /// Inspect or rewrite an outbound response. The default is a pass-through.
+    async fn on_response(&self, _ctx: &FilterContext, resp: Vec<u8>) -> Vec<u8> {
+        resp
+    }
+}
+
+/// Run the request half of a filter chain, short-circuiting on the first
+/// [`FilterOutcome::Reject`].
+pub async fn apply_request(
+    filters: &[std::sync::Arc<dyn ProxyFilter>],
+    ctx: &FilterContext,
+    mut req: Vec<u8>,
+) -> Result<Vec<u8>, Status> {
+    for filter in filters {
+        match filter.on_request(ctx, req.clone()).await {
+            FilterOutcome::Pass => {}
+            FilterOutcome::Modify(next) => req = next,
+            FilterOutcome::Reject(status) => {
+                tracing::info!(
+                    correlation_id = %ctx.correlation_id,
+                    namespace = %ctx.namespace,
+                    operation = ?ctx.operation,
+                    "request rejected by filter"
+                );
+                return Err(status);
+            }
+        }
+    }
+    Ok(req)
+}
+
+/// Run the response half of a filter chain, in order.
+pub async fn apply_response(
+    filters: &[std::sync::Arc<dyn ProxyFilter>],
+    ctx: &FilterContext,
+    mut resp: Vec<u8>,
+) -> Vec<u8> {
+    for filter in filters {
+        resp = filter.on_response(ctx, resp).await;
+    }
+    resp
+}
+
+/// Mint a monotonically increasing correlation id.
+fn next_correlation_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("req-{n:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    struct RejectAll;
+
+    #[tonic::async_trait]
+    impl ProxyFilter for RejectAll {
+        async fn on_request(&self, _ctx: &FilterContext, _req: Vec<u8>) -> FilterOutcome {
+            FilterOutcome::Reject(Status::permission_denied("denied"))
+        }
+    }
+
+    struct UppercasePrefix;
+
+    #[tonic::async_trait]
+    impl ProxyFilter for UppercasePrefix {
+        async fn on_request(&self, _ctx: &FilterContext, mut req: Vec<u8>) -> FilterOutcome {
+            req.make_ascii_uppercase();
+            FilterOutcome::Modify(req)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reject_short_circuits() {
+        let filters: Vec<Arc<dyn ProxyFilter>> = vec![Arc::new(RejectAll)];
+        let ctx = FilterContext::new("default", OperationKind::Set);
+        let result = apply_request(&filters, &ctx, b"hello".to_vec()).await;
+        assert_eq!(result.unwrap_err().code(), tonic::Code::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn test_modify_rewrites_request() {
+        let filters: Vec<Arc<dyn ProxyFilter>> = vec![Arc::new(UppercasePrefix)];
+        let ctx = FilterContext::new("default", OperationKind::Set);
+        let out = apply_request(&filters, &ctx, b"hello".to_vec()).await.unwrap();
+        assert_eq!(out, b"HELLO".to_vec());
+    }
+
+    #[test]
+    fn test_correlation_ids_are_unique() {
+        let a = FilterContext::new("ns", OperationKind::Get).correlation_id;
+        let b = FilterContext::new("ns", OperationKind::Get).correlation_id;
+        assert_ne!(a, b);
+    }
+}
@@ -1,103 +1,247 @@
 //! gRPC server for Prism proxy
-
+//!
+//! The server is driven by an explicit, event-driven lifecycle state machine
+//! (modelled on Apollo Router's reload loop). Configuration arrives as
+//! [`ServerEvent::UpdateConfig`] events; while `Running`, a new config builds a
+//! fresh router and atomically swaps it behind the live service so in-flight
+//! requests keep using the old routing table while new requests pick up the new
+//! one. A reload changes routing only: the listener stays bound to its original
+//! address, and patterns are neither started nor stopped.
+
+mod conn_track;
+pub mod filter;
 mod keyvalue;
 
+use crate::config::ProxyConfig;
+use conn_track::{ConnTrackLayer, ConnTracker};
+use filter::ProxyFilter;
+use crate::pattern::PatternManager;
 use crate::proto::interfaces::keyvalue::key_value_basic_interface_server::KeyValueBasicInterfaceServer;
-use crate::router::Router;
+use crate::router::{Router, SharedRouter};
 use keyvalue::KeyValueService;
 use std::net::SocketAddr;
-use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::oneshot;
-use tokio::sync::RwLock;
+use tokio::sync::{oneshot, RwLock};
 use tokio::time::sleep;
 use tonic::transport::Server;
 
-/// Server drain state
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum DrainState {
-    /// Server is running normally
-    Running,
-    /// Server is draining connections (rejecting new connections, completing existing work)
+/// Lifecycle state of the proxy server.
+#[derive(Debug, Clone)]
+pub enum ServerState {
+    /// Initial state, holding the config the server will start with.
+    Startup { config: ProxyConfig },
+    /// Serving traffic with the given config; the routing table lives in the
+    /// shared router.
+    Running { config: ProxyConfig },
+    /// Draining in-flight work, rejecting new connections.
     Draining { started_at: Instant },
-    /// Server is stopping (all patterns stopped)
+    /// All patterns stopped, tearing the listener down.
     Stopping,
+    /// A fatal error occurred (e.g. invalid config or bind failure).
+    Errored { reason: String },
+}
+
+/// Events that drive the server state machine.
+#[derive(Debug)]
+pub enum ServerEvent {
+    /// Apply a new configuration (hot reload while `Running`).
+    UpdateConfig(ProxyConfig),
+    /// Begin draining.
+    Drain,
+    /// Stop the server.
+    Shutdown,
+    /// The config source has no further updates; keep serving the last config.
+    NoMoreConfig,
 }
 
 /// Proxy server
 pub struct ProxyServer {
-    router: Arc<Router>,
+    /// Shared routing table, swapped atomically on reload.
+    router: SharedRouter,
+    /// Pattern manager used to rebuild routers on reload.
+    pattern_manager: Arc<PatternManager>,
     listen_address: String,
     shutdown_tx: Option<oneshot::Sender<()>>,
-    /// Drain state tracking
-    drain_state: Arc<RwLock<DrainState>>,
-    /// Active frontend connection count
-    active_connections: Arc<AtomicUsize>,
+    /// Current lifecycle state.
+    state: Arc<RwLock<ServerState>>,
+    /// Active frontend connection tracker, updated by the tonic layer.
+    connections: ConnTracker,
+    /// Ordered middleware chain applied to KeyValue requests/responses.
+    filters: Vec<Arc<dyn ProxyFilter>>,
 }
 
 impl ProxyServer {
     /// Create a new proxy server
     pub fn new(router: Arc<Router>, listen_address: String) -> Self {
+        let pattern_manager = router.pattern_manager.clone();
         Self {
-            router,
+            router: Arc::new(RwLock::new(router)),
+            pattern_manager,
             listen_address,
             shutdown_tx: None,
-            drain_state: Arc::new(RwLock::new(DrainState::Running)),
-            active_connections: Arc::new(AtomicUsize::new(0)),
+            state: Arc::new(RwLock::new(ServerState::Startup {
+                config: ProxyConfig {
+                    listen_address: listen_address.clone(),
+                    patterns: Vec::new(),
+                },
+            })),
+            connections: ConnTracker::default(),
+            filters: Vec::new(),
         }
     }
 
-    /// Get current drain state
-    pub async fn get_drain_state(&self) -> DrainState {
-        self.drain_state.read().await.clone()
+    /// Register a filter, appended to the end of the chain.
+    pub fn register_filter(&mut self, filter: Arc<dyn ProxyFilter>) {
+        self.filters.push(filter);
+    }
+
+    /// Get the current lifecycle state.
+    pub async fn state(&self) -> ServerState {
+        self.state.read().await.clone()
+    }
+
+    /// Whether the server is currently draining.
+    pub async fn is_draining(&self) -> bool {
+        matches!(&*self.state.read().await, ServerState::Draining { .. })
     }
 
     /// Get active connection count
     pub fn get_active_connections(&self) -> usize {
-        self.active_connections.load(Ordering::Relaxed)
+        self.connections.active()
     }
 
-    /// Start the server
+    /// Start the server.
+    ///
+    /// The listen address is parsed and the listener is bound up front, so an
+    /// invalid address or a bind failure transitions the machine to
+    /// [`ServerState::Errored`] and returns an error rather than panicking
+    /// inside the spawned serve task. `start` only returns once the server is
+    /// actually accepting connections, signalled over a readiness channel, so
+    /// callers no longer need to sleep.
     pub async fn start(&mut self) -> crate::Result<()> {
-        let addr: SocketAddr = self.listen_address.parse()?;
+        let addr: SocketAddr = match self.listen_address.parse() {
+            Ok(addr) => addr,
+            Err(e) => {
+                let reason = format!("invalid listen address {}: {e}", self.listen_address);
+                *self.state.write().await = ServerState::Errored {
+                    reason: reason.clone(),
+                };
+                anyhow::bail!(reason);
+            }
+        };
         tracing::info!("Starting proxy server on {}", addr);
 
-        // Create KeyValue service
-        let keyvalue_service = KeyValueService::new(self.router.clone());
+        // Bind before spawning so bind errors surface synchronously.
+        let listener = match tokio::net::TcpListener::bind(addr).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                let reason = format!("failed to bind {addr}: {e}");
+                *self.state.write().await = ServerState::Errored {
+                    reason: reason.clone(),
+                };
+                anyhow::bail!(reason);
+            }
+        };
+        let incoming =
+            tonic::transport::server::TcpIncoming::from_listener(listener, true, None)
+                .map_err(|e| anyhow::anyhow!("failed to wrap listener: {e}"))?;
+
+        let keyvalue_service =
+            KeyValueService::new(self.router.clone()).with_filters(self.filters.clone());
 
-        // Create shutdown channel
         let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
         self.shutdown_tx = Some(shutdown_tx);
 
-        // Start gRPC server
+        // Fires once the serve task is about to accept connections.
+        let (ready_tx, ready_rx) = oneshot::channel::<()>();
+
+        let layer = ConnTrackLayer::new(self.connections.clone());
         tokio::spawn(async move {
-            Server::builder()
+            let _ = ready_tx.send(());
+            if let Err(e) = Server::builder()
+                .layer(layer)
                 .add_service(KeyValueBasicInterfaceServer::new(keyvalue_service))
-                .serve_with_shutdown(addr, async {
+                .serve_with_incoming_shutdown(incoming, async {
                     shutdown_rx.await.ok();
                 })
                 .await
-                .expect("gRPC server failed");
+            {
+                tracing::error!(error = %e, "gRPC server exited with error");
+            }
         });
 
-        // Give server time to start
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        // Wait for readiness instead of sleeping.
+        let _ = ready_rx.await;
 
+        *self.state.write().await = ServerState::Running {
+            config: ProxyConfig {
+                listen_address: self.listen_address.clone(),
+                patterns: Vec::new(),
+            },
+        };
+        Ok(())
+    }
+
+    /// Handle a single lifecycle event, advancing the state machine.
+    pub async fn handle_event(&mut self, event: ServerEvent) -> crate::Result<()> {
+        match event {
+            ServerEvent::UpdateConfig(config) => self.update_config(config).await,
+            ServerEvent::Drain => {
+                *self.state.write().await = ServerState::Draining {
+                    started_at: Instant::now(),
+                };
+                self.connections.set_draining(true);
+                Ok(())
+            }
+            ServerEvent::Shutdown => self.shutdown().await,
+            ServerEvent::NoMoreConfig => {
+                tracing::debug!("no more config updates; keeping current configuration");
+                Ok(())
+            }
+        }
+    }
+
+    /// Apply a new configuration while `Running`, atomically swapping the router.
+    async fn update_config(&mut self, config: ProxyConfig) -> crate::Result<()> {
+        let running = matches!(&*self.state.read().await, ServerState::Running { .. });
+        if !running {
+            tracing::warn!("ignoring UpdateConfig while not Running");
+            return Ok(());
+        }
+
+        tracing::info!(
+            patterns = config.patterns.len(),
+            "hot-reloading configuration, rebuilding router"
+        );
+
+        // Build a fresh router with the new namespace -> pattern mapping.
+        let new_router = Arc::new(Router::new(self.pattern_manager.clone()));
+        for pattern in &config.patterns {
+            new_router
+                .register_route(pattern.name.clone(), pattern.name.clone())
+                .await;
+        }
+
+        // Atomic swap: new requests see the new router; in-flight requests that
+        // already cloned the old Arc keep using it until they finish.
+        *self.router.write().await = new_router;
+
+        *self.state.write().await = ServerState::Running { config };
+        tracing::info!("configuration reloaded");
         Ok(())
     }
 
     /// Shutdown the server
     pub async fn shutdown(&mut self) -> crate::Result<()> {
         tracing::info!("Shutting down proxy server");
+        *self.state.write().await = ServerState::Stopping;
 
         if let Some(shutdown_tx) = self.shutdown_tx.take() {
             let _ = shutdown_tx.send(());
         }
 
-        // Give server time to shutdown
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-
+        sleep(Duration::from_millis(100)).await;
         Ok(())
     }
 
@@ -120,19 +264,17 @@ impl ProxyServer {
             "🔸 Starting drain-on-shutdown sequence"
         );
 
-        // Phase 1: Enter drain mode
-        {
-            let mut state = self.drain_state.write().await;
-            *state = DrainState::Draining {
-                started_at: Instant::now(),
-            };
-        }
+        // Phase 1: Enter drain mode — the layer now rejects new requests with
+        // Status::unavailable while existing ones are allowed to complete.
+        *self.state.write().await = ServerState::Draining {
+            started_at: Instant::now(),
+        };
+        self.connections.set_draining(true);
         tracing::info!("🔸 DRAIN MODE: Rejecting new connections, completing existing work");
 
         // Phase 2: Signal pattern runners to drain
         tracing::info!("🔸 Signaling pattern runners to drain");
         if let Err(e) = self
-            .router
             .pattern_manager
             .drain_all_patterns(timeout.as_secs() as i32, reason.clone())
             .await
@@ -142,16 +284,16 @@ impl ProxyServer {
 
         // Phase 3: Wait for frontend connections to complete
         tracing::info!(
-            active = self.active_connections.load(Ordering::Relaxed),
+            active = self.connections.active(),
             "⏳ Waiting for frontend connections to drain"
         );
 
         let poll_interval = Duration::from_millis(100);
         let deadline = Instant::now() + timeout;
 
-        while self.active_connections.load(Ordering::Relaxed) > 0 {
+        while self.connections.active() > 0 {
             if Instant::now() > deadline {
-                let remaining = self.active_connections.load(Ordering::Relaxed);
+                let remaining = self.connections.active();
                 tracing::warn!(
                     remaining_connections = remaining,
                     "⏱️  Drain timeout exceeded, forcing shutdown"
@@ -164,18 +306,10 @@ impl ProxyServer {
         tracing::info!("✅ Frontend connections drained");
 
         // Phase 4: Stop pattern runners
-        {
-            let mut state = self.drain_state.write().await;
-            *state = DrainState::Stopping;
-        }
+        *self.state.write().await = ServerState::Stopping;
         tracing::info!("🔹 STOPPING MODE: Stopping pattern runners");
 
-        if let Err(e) = self
-            .router
-            .pattern_manager
-            .stop_all_patterns()
-            .await
-        {
+        if let Err(e) = self.pattern_manager.stop_all_patterns().await {
             tracing::warn!(error = %e, "Failed to stop pattern runners, continuing shutdown");
         }
 
@@ -184,7 +318,6 @@ impl ProxyServer {
             let _ = shutdown_tx.send(());
         }
 
-        // Give server time to shutdown
         sleep(Duration::from_millis(100)).await;
 
         tracing::info!("✅ Proxy shutdown complete");
@@ -211,10 +344,20 @@ mod tests {
         let router = Arc::new(Router::new(pattern_manager));
         let mut server = ProxyServer::new(router, "127.0.0.1:19980".to_string());
 
-        // Start server
         server.start().await.expect("Failed to start server");
+        assert!(matches!(server.state().await, ServerState::Running { .. }));
 
-        // Shutdown server
         server.shutdown().await.expect("Failed to shutdown server");
+        assert!(matches!(server.state().await, ServerState::Stopping));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_address_errors_instead_of_panicking() {
+        let pattern_manager = Arc::new(PatternManager::new());
+        let router = Arc::new(Router::new(pattern_manager));
+        let mut server = ProxyServer::new(router, "not-an-address".to_string());
+
+        assert!(server.start().await.is_err());
+        assert!(matches!(server.state().await, ServerState::Errored { .. }));
     }
 }
@@ -1,5 +1,6 @@
 //! Prism Proxy - Main entry point
 
+use prism_proxy::shutdown::ShutdownConfig;
 use prism_proxy::{PatternManager, ProxyConfig, ProxyServer, Router};
 use std::sync::Arc;
 use tracing::{error, info};
@@ -13,8 +14,12 @@ async fn main() -> anyhow::Result<()> {
 
     info!("Starting Prism Proxy v{}", env!("CARGO_PKG_VERSION"));
 
-    // Load configuration
-    let config = ProxyConfig::default();
+    // Load configuration, letting the environment override the listen address
+    // so harnesses (and deployments) can bind a port other than the default.
+    let mut config = ProxyConfig::default();
+    if let Ok(addr) = std::env::var("PRISM_LISTEN_ADDRESS") {
+        config.listen_address = addr;
+    }
     info!("Loaded configuration: {:?}", config);
 
     // Create pattern manager
@@ -52,12 +57,14 @@ async fn main() -> anyhow::Result<()> {
     // Wait for shutdown signal
     tokio::signal::ctrl_c().await?;
 
-    // Graceful shutdown
+    // Graceful shutdown: drain every pattern concurrently under a bounded
+    // timing policy instead of stopping them one at a time.
     info!("Received shutdown signal, stopping patterns...");
-    for pattern_config in &config.patterns {
-        if let Err(e) = pattern_manager.stop_pattern(&pattern_config.name).await {
-            error!("Failed to stop pattern {}: {}", pattern_config.name, e);
-        }
+    for (name, outcome) in pattern_manager
+        .graceful_shutdown(ShutdownConfig::default())
+        .await
+    {
+        info!("Pattern {} shutdown: {:?}", name, outcome);
     }
 
     server.shutdown().await?;
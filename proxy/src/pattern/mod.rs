@@ -9,14 +9,165 @@
 mod client;
 
 use client::PatternClient;
-use std::collections::HashMap;
+use crate::shutdown::{ShutdownConfig, ShutdownOutcome, Tripwire};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::process::Child;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, Notify, RwLock};
+use tokio::task::JoinHandle;
 use tokio::time::sleep;
 
+/// Strategy governing the delay between reconnection attempts after a pattern's
+/// backend connection drops.
+#[derive(Debug, Clone)]
+pub enum ReconnectStrategy {
+    /// Wait a fixed interval between every attempt.
+    Fixed { interval: Duration },
+    /// Exponential backoff with optional jitter, capped at `max`.
+    ExponentialBackoff {
+        base: Duration,
+        factor: u32,
+        max: Duration,
+        jitter: bool,
+    },
+}
+
+impl ReconnectStrategy {
+    /// Compute the backoff delay for a given zero-based attempt number.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        match self {
+            ReconnectStrategy::Fixed { interval } => *interval,
+            ReconnectStrategy::ExponentialBackoff {
+                base,
+                factor,
+                max,
+                jitter,
+            } => {
+                let scaled = base
+                    .checked_mul(factor.saturating_pow(attempt).max(1))
+                    .unwrap_or(*max)
+                    .min(*max);
+                if *jitter {
+                    apply_jitter(scaled)
+                } else {
+                    scaled
+                }
+            }
+        }
+    }
+}
+
+impl Default for ReconnectStrategy {
+    fn default() -> Self {
+        ReconnectStrategy::ExponentialBackoff {
+            base: Duration::from_millis(100),
+            factor: 2,
+            max: Duration::from_secs(30),
+            jitter: true,
+        }
+    }
+}
+
+/// Apply jitter to a backoff duration, scaling it to between half and all of its
+/// nominal value with a dependency-free pseudo-random fraction drawn from the
+/// current clock's sub-second component.
+fn apply_jitter(delay: Duration) -> Duration {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let frac = (nanos % 1_000) as f64 / 1_000.0;
+    delay.mul_f64(0.5 + 0.5 * frac)
+}
+
+/// Record a reconnection attempt: update the shared [`ConnectionState`], sleep
+/// the strategy's backoff, and return `false` once the retry budget is
+/// exhausted (so the heartbeat task should stop). With `fail_open` set, an
+/// exhausted budget instead marks the connection `Connected` and returns `true`.
+async fn note_reconnect(
+    state: &Arc<RwLock<ConnectionState>>,
+    config: &HeartbeatConfig,
+    name: &str,
+    attempt: u32,
+    error: &str,
+) -> bool {
+    if let Some(max) = config.max_retries {
+        if attempt > max {
+            if config.fail_open {
+                tracing::warn!(
+                    pattern = %name,
+                    attempt,
+                    %error,
+                    "heartbeat retries exhausted, failing open (staying reachable)"
+                );
+                *state.write().await = ConnectionState::Connected;
+                return true;
+            }
+            tracing::error!(pattern = %name, attempt, %error, "heartbeat retries exhausted, marking connection dead");
+            *state.write().await = ConnectionState::Dead;
+            return false;
+        }
+    }
+
+    let delay = config.strategy.delay(attempt.saturating_sub(1));
+    tracing::warn!(
+        pattern = %name,
+        attempt,
+        backoff_ms = delay.as_millis(),
+        %error,
+        "pattern heartbeat failed, reconnecting"
+    );
+    *state.write().await = ConnectionState::Reconnecting {
+        attempt,
+        next_at: Instant::now() + delay,
+    };
+    sleep(delay).await;
+    true
+}
+
+/// Heartbeat and reconnection configuration carried per pattern.
+#[derive(Debug, Clone)]
+pub struct HeartbeatConfig {
+    /// Interval between liveness pings.
+    pub interval: Duration,
+    /// Per-ping timeout.
+    pub timeout: Duration,
+    /// Backoff strategy used while reconnecting.
+    pub strategy: ReconnectStrategy,
+    /// Maximum reconnection attempts before declaring the connection `Dead`.
+    /// `None` retries forever (fail-open never latches to `Dead`).
+    pub max_retries: Option<u32>,
+    /// When true, a failed ping keeps routing to the backend (fail-open) rather
+    /// than fast-failing once the retry budget is exhausted.
+    pub fail_open: bool,
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(5),
+            timeout: Duration::from_secs(2),
+            strategy: ReconnectStrategy::default(),
+            max_retries: Some(10),
+            fail_open: false,
+        }
+    }
+}
+
+/// Liveness state of the proxy's connection to a pattern backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// The last heartbeat succeeded.
+    Connected,
+    /// A heartbeat failed and the connection is being re-established.
+    Reconnecting { attempt: u32, next_at: Instant },
+    /// The retry budget was exhausted; the connection is given up on.
+    Dead,
+}
+
 /// Pattern status enumeration
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PatternStatus {
@@ -28,6 +179,8 @@ pub enum PatternStatus {
     Running,
     /// Pattern is unhealthy but still running
     Degraded,
+    /// Pattern crashed and is being restarted by the supervisor
+    Restarting,
     /// Pattern is shutting down
     Stopping,
     /// Pattern has stopped
@@ -36,6 +189,536 @@ pub enum PatternStatus {
     Failed(String),
 }
 
+impl PatternStatus {
+    /// Whether the pattern is able to serve data-plane traffic.
+    ///
+    /// A `Degraded` pattern is still reachable, so the router may keep routing
+    /// to it; only terminal or not-yet-ready states are excluded.
+    pub fn is_serving(&self) -> bool {
+        matches!(self, PatternStatus::Running | PatternStatus::Degraded)
+    }
+}
+
+/// Maximum automatic restarts permitted within [`RESTART_WINDOW`] before a
+/// pattern latches to `Failed` and the supervisor gives up on it.
+const MAX_RESTARTS: u32 = 5;
+
+/// Rolling window over which automatic restarts are counted.
+const RESTART_WINDOW: Duration = Duration::from_secs(60);
+
+/// Per-restart timeout: bring-up that exceeds this is treated as a failed
+/// restart.
+const RESTART_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Policy governing whether the supervisor restarts a pattern after its process
+/// exits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartPolicy {
+    /// Always restart, regardless of exit status.
+    Always,
+    /// Restart only on a non-clean exit (the default).
+    OnFailure,
+    /// Never restart automatically.
+    Never,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        RestartPolicy::OnFailure
+    }
+}
+
+impl RestartPolicy {
+    /// Whether an exit with the given success flag should trigger a restart.
+    fn should_restart(self, clean_exit: bool) -> bool {
+        match self {
+            RestartPolicy::Always => true,
+            RestartPolicy::OnFailure => !clean_exit,
+            RestartPolicy::Never => false,
+        }
+    }
+
+    /// Parse a policy from its lower-case config spelling, falling back to the
+    /// default for unknown values.
+    fn from_config(value: &str) -> Self {
+        match value.to_ascii_lowercase().as_str() {
+            "always" => RestartPolicy::Always,
+            "never" => RestartPolicy::Never,
+            _ => RestartPolicy::OnFailure,
+        }
+    }
+}
+
+/// Supervisor restart policy plus the crash-loop-breaker parameters.
+#[derive(Debug, Clone)]
+pub struct RestartConfig {
+    /// When to restart after an exit.
+    pub policy: RestartPolicy,
+    /// Initial backoff before the first restart attempt.
+    pub base_backoff: Duration,
+    /// Upper bound on the restart backoff.
+    pub max_backoff: Duration,
+    /// Restarts permitted within `window` before the crash-loop breaker trips.
+    pub max_restarts: u32,
+    /// Rolling window over which restarts are counted.
+    pub window: Duration,
+}
+
+impl RestartConfig {
+    /// Backoff before the `attempt`-th restart (1-based), doubling from
+    /// `base_backoff` and capped at `max_backoff` — the same scheme the
+    /// connection layer uses for reconnection.
+    fn backoff_for(&self, attempt: u32) -> Duration {
+        let shift = attempt.saturating_sub(1).min(31);
+        self.base_backoff
+            .checked_mul(1u32 << shift)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RestartConfig {
+    fn default() -> Self {
+        Self {
+            policy: RestartPolicy::default(),
+            base_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(30),
+            max_restarts: MAX_RESTARTS,
+            window: RESTART_WINDOW,
+        }
+    }
+}
+
+/// Configuration for the background health monitor.
+#[derive(Debug, Clone)]
+pub struct MonitorConfig {
+    /// Interval between polling sweeps.
+    pub interval: Duration,
+    /// Consecutive failed health checks before a `Running` pattern is marked
+    /// `Degraded`.
+    pub failure_threshold: u32,
+    /// Consecutive successful health checks before a `Degraded` pattern recovers
+    /// to `Running`.
+    pub recovery_threshold: u32,
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(5),
+            failure_threshold: 3,
+            recovery_threshold: 2,
+        }
+    }
+}
+
+/// A status transition produced by the health monitor, so the manager can feed
+/// terminal failures into the supervisor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HealthTransition {
+    /// A healthy pattern accumulated enough failures to be marked `Degraded`.
+    Degraded,
+    /// A `Degraded` pattern accumulated enough successes to recover.
+    Recovered,
+    /// A `Degraded` pattern kept failing and was parked in `Failed`.
+    Failed,
+}
+
+/// Handle to a running background health monitor. Dropping it detaches the
+/// monitor; call [`MonitorHandle::shutdown`] to stop it deterministically.
+pub struct MonitorHandle {
+    shutdown: Arc<Notify>,
+    task: JoinHandle<()>,
+}
+
+impl MonitorHandle {
+    /// Signal the monitor loop to stop and wait for it to finish.
+    pub async fn shutdown(self) {
+        self.shutdown.notify_one();
+        let _ = self.task.await;
+    }
+}
+
+/// Upper bound on OS ephemeral-port probes before giving up, guarding against a
+/// pathological run of ports that are handed out but not yet recorded.
+const MAX_PORT_PROBES: usize = 16;
+
+/// How a pattern's gRPC port is chosen.
+#[derive(Debug, Clone)]
+pub enum PortStrategy {
+    /// Bind `127.0.0.1:0`, let the OS assign a free port, then hand it to the
+    /// spawned child. Best for local development where any free port will do.
+    Ephemeral,
+    /// Draw the next free port from an inclusive range, skipping ports that are
+    /// already allocated or otherwise in use.
+    Range { start: u16, end: u16 },
+}
+
+impl Default for PortStrategy {
+    fn default() -> Self {
+        PortStrategy::Ephemeral
+    }
+}
+
+/// Hands out unique gRPC ports for spawned patterns, tracking in-use ports so
+/// two patterns started concurrently can never collide on the same port.
+///
+/// Cloning shares the in-use set, so every [`PatternManager`] clone allocates
+/// against the same pool.
+#[derive(Clone, Default)]
+pub struct PortAllocator {
+    strategy: PortStrategy,
+    in_use: Arc<Mutex<HashSet<u16>>>,
+}
+
+impl PortAllocator {
+    /// An allocator that asks the OS for an ephemeral port each time.
+    pub fn ephemeral() -> Self {
+        Self {
+            strategy: PortStrategy::Ephemeral,
+            in_use: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// An allocator that draws from an inclusive `[start, end]` port range.
+    pub fn range(start: u16, end: u16) -> Self {
+        Self {
+            strategy: PortStrategy::Range { start, end },
+            in_use: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// Allocate a unique, currently-free port and record it as in-use. The port
+    /// must later be returned with [`Self::release`] so it can be reused.
+    pub async fn allocate(&self) -> crate::Result<u16> {
+        let mut in_use = self.in_use.lock().await;
+        match &self.strategy {
+            PortStrategy::Ephemeral => {
+                for _ in 0..MAX_PORT_PROBES {
+                    let port = bind_ephemeral_port()?;
+                    // `insert` is false if the OS re-handed a port we already
+                    // reserved but whose child has not yet bound it; probe again.
+                    if in_use.insert(port) {
+                        return Ok(port);
+                    }
+                }
+                anyhow::bail!("failed to obtain a free ephemeral port");
+            }
+            PortStrategy::Range { start, end } => {
+                for port in *start..=*end {
+                    if !in_use.contains(&port) && port_is_free(port) {
+                        in_use.insert(port);
+                        return Ok(port);
+                    }
+                }
+                anyhow::bail!("no free port available in range {start}-{end}");
+            }
+        }
+    }
+
+    /// Return a previously allocated port to the pool.
+    pub async fn release(&self, port: u16) {
+        self.in_use.lock().await.remove(&port);
+    }
+}
+
+/// Bind an OS-assigned ephemeral port, read it back, and immediately release it
+/// so the spawned child can claim it.
+fn bind_ephemeral_port() -> crate::Result<u16> {
+    let listener = std::net::TcpListener::bind(("127.0.0.1", 0))?;
+    Ok(listener.local_addr()?.port())
+}
+
+/// Whether a specific port on loopback is currently bindable.
+fn port_is_free(port: u16) -> bool {
+    std::net::TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+/// A resolved gRPC endpoint for a pattern, as published in the [`RouteTable`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    /// gRPC URL the proxy dials, e.g. `http://localhost:9001`.
+    pub url: String,
+    /// Port the pattern's child process was handed.
+    pub port: u16,
+}
+
+/// Serving state of a pattern's route.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteStatus {
+    /// The endpoint is live and requests may be forwarded to it.
+    Active,
+    /// The endpoint is being (re)established — e.g. during a restart — so
+    /// requests should fast-fail rather than hit a dead address.
+    Invalid,
+}
+
+/// A single route-table entry.
+#[derive(Debug, Clone)]
+struct Route {
+    endpoint: Endpoint,
+    status: RouteStatus,
+}
+
+/// Per-pattern endpoint/route map, analogous to the endpoints map a messaging
+/// client keeps. [`PatternManager`] publishes a route when a pattern reaches
+/// `Running` and invalidates it the moment a restart begins, so
+/// [`PatternManager::resolve`] never returns a dead endpoint.
+///
+/// Cloning shares the underlying map, so every [`PatternManager`] clone sees the
+/// same routes.
+#[derive(Clone, Default)]
+pub struct RouteTable {
+    routes: Arc<RwLock<HashMap<String, Route>>>,
+}
+
+impl RouteTable {
+    /// An empty route table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Publish (or replace) an active route for a pattern.
+    pub async fn publish(&self, name: &str, url: String, port: u16) {
+        self.routes.write().await.insert(
+            name.to_string(),
+            Route {
+                endpoint: Endpoint { url, port },
+                status: RouteStatus::Active,
+            },
+        );
+    }
+
+    /// Mark a pattern's route invalid without dropping its endpoint, so an
+    /// in-flight restart stops routing immediately but the entry can be
+    /// re-activated atomically once bring-up succeeds.
+    pub async fn invalidate(&self, name: &str) {
+        if let Some(route) = self.routes.write().await.get_mut(name) {
+            route.status = RouteStatus::Invalid;
+        }
+    }
+
+    /// Drop a pattern's route entirely.
+    pub async fn remove(&self, name: &str) {
+        self.routes.write().await.remove(name);
+    }
+
+    /// Resolve a pattern to its endpoint, but only while its route is active.
+    pub async fn resolve(&self, name: &str) -> Option<Endpoint> {
+        let routes = self.routes.read().await;
+        routes
+            .get(name)
+            .filter(|route| route.status == RouteStatus::Active)
+            .map(|route| route.endpoint.clone())
+    }
+
+    /// Current route status for a pattern, if it has an entry.
+    pub async fn status(&self, name: &str) -> Option<RouteStatus> {
+        self.routes.read().await.get(name).map(|route| route.status)
+    }
+}
+
+/// The gRPC lifecycle surface a [`Pattern`] drives.
+///
+/// Abstracting the RPCs behind a trait lets the supervision, monitoring, and
+/// drain logic be exercised in-process against a scripted mock — no real binary
+/// or socket required. The concrete [`PatternClient`] is one implementor;
+/// connection establishment stays an inherent constructor on the client because
+/// it yields the implementor rather than acting on one.
+#[tonic::async_trait]
+pub trait PatternControl: Send + Sync {
+    /// Initialize the pattern via its `Initialize` RPC.
+    async fn initialize(
+        &mut self,
+        name: String,
+        version: String,
+        config: serde_json::Value,
+    ) -> crate::Result<()>;
+
+    /// Start the pattern, returning the data-plane endpoint it advertises.
+    async fn start(&mut self) -> crate::Result<String>;
+
+    /// Drain the pattern ahead of shutdown.
+    async fn drain(&mut self, timeout_seconds: i32, reason: String) -> crate::Result<()>;
+
+    /// Stop the pattern.
+    async fn stop(&mut self, timeout_seconds: i32) -> crate::Result<()>;
+
+    /// Report the pattern's current health.
+    async fn health_check(&mut self) -> crate::Result<PatternStatus>;
+}
+
+#[tonic::async_trait]
+impl PatternControl for PatternClient {
+    async fn initialize(
+        &mut self,
+        name: String,
+        version: String,
+        config: serde_json::Value,
+    ) -> crate::Result<()> {
+        // Inherent methods shadow the trait methods, so this delegates rather
+        // than recursing. Any declared metadata is surfaced by the inherent
+        // method and not needed here.
+        PatternClient::initialize(self, name, version, config).await?;
+        Ok(())
+    }
+
+    async fn start(&mut self) -> crate::Result<String> {
+        PatternClient::start(self).await
+    }
+
+    async fn drain(&mut self, timeout_seconds: i32, reason: String) -> crate::Result<()> {
+        PatternClient::drain(self, timeout_seconds, reason).await
+    }
+
+    async fn stop(&mut self, timeout_seconds: i32) -> crate::Result<()> {
+        PatternClient::stop(self, timeout_seconds).await
+    }
+
+    async fn health_check(&mut self) -> crate::Result<PatternStatus> {
+        PatternClient::health_check(self).await
+    }
+}
+
+/// Extra launch configuration applied to a pattern's child process on top of
+/// the `--grpc-port` argument the proxy always supplies.
+///
+/// Parsed from the `"launch"` object of a pattern's config (see
+/// [`Pattern::with_config`]) so operators can hand credentials and feature
+/// flags to heterogeneous backends without recompiling the proxy. An empty
+/// spec leaves the child's environment, arguments, and working directory
+/// untouched.
+#[derive(Debug, Clone, Default)]
+pub struct LaunchSpec {
+    /// Environment variables injected into the child, applied after any
+    /// `clear_env`. A `BTreeMap` keeps the resolved-spec log deterministic.
+    env: BTreeMap<String, String>,
+    /// Start the child with an empty environment before applying `env`, for
+    /// hermetic backends that must not inherit the proxy's environment.
+    clear_env: bool,
+    /// Extra command-line arguments appended after `--grpc-port <port>`.
+    args: Vec<String>,
+    /// Working directory to run the child in; inherits the proxy's when `None`.
+    working_dir: Option<PathBuf>,
+}
+
+impl LaunchSpec {
+    /// Parse and validate a launch spec from a pattern config value.
+    ///
+    /// Accepts a config whose optional `"launch"` field is an object with
+    /// `env` (object of string→string), `clear_env` (bool), `args` (array of
+    /// strings), and `working_dir` (string). A missing `"launch"` field yields
+    /// the empty spec. Wrong types or empty env names are rejected here so a
+    /// misconfigured pattern fails fast at registration rather than at spawn.
+    fn from_config(config: &serde_json::Value) -> crate::Result<Self> {
+        let launch = match config.get("launch") {
+            None | Some(serde_json::Value::Null) => return Ok(Self::default()),
+            Some(v) => v,
+        };
+        let obj = launch
+            .as_object()
+            .ok_or_else(|| anyhow::anyhow!("`launch` must be an object"))?;
+
+        let mut env = BTreeMap::new();
+        if let Some(value) = obj.get("env") {
+            let map = value
+                .as_object()
+                .ok_or_else(|| anyhow::anyhow!("`launch.env` must be an object"))?;
+            for (key, val) in map {
+                if key.is_empty() || key.contains('=') || key.contains('\0') {
+                    anyhow::bail!("invalid environment variable name {key:?}");
+                }
+                let val = val
+                    .as_str()
+                    .ok_or_else(|| anyhow::anyhow!("`launch.env.{key}` must be a string"))?;
+                if val.contains('\0') {
+                    anyhow::bail!("environment value for {key:?} contains a NUL byte");
+                }
+                env.insert(key.clone(), val.to_string());
+            }
+        }
+
+        let clear_env = match obj.get("clear_env") {
+            None | Some(serde_json::Value::Null) => false,
+            Some(v) => v
+                .as_bool()
+                .ok_or_else(|| anyhow::anyhow!("`launch.clear_env` must be a boolean"))?,
+        };
+
+        let mut args = Vec::new();
+        if let Some(value) = obj.get("args") {
+            let list = value
+                .as_array()
+                .ok_or_else(|| anyhow::anyhow!("`launch.args` must be an array"))?;
+            for arg in list {
+                let arg = arg
+                    .as_str()
+                    .ok_or_else(|| anyhow::anyhow!("`launch.args` entries must be strings"))?;
+                args.push(arg.to_string());
+            }
+        }
+
+        let working_dir = match obj.get("working_dir") {
+            None | Some(serde_json::Value::Null) => None,
+            Some(v) => Some(PathBuf::from(
+                v.as_str()
+                    .ok_or_else(|| anyhow::anyhow!("`launch.working_dir` must be a string"))?,
+            )),
+        };
+
+        Ok(Self {
+            env,
+            clear_env,
+            args,
+            working_dir,
+        })
+    }
+}
+
+/// Whether an environment variable or argument flag name looks like it carries
+/// a secret, used to decide what to mask before logging the launch spec.
+fn looks_like_secret(name: &str) -> bool {
+    let upper = name.to_uppercase();
+    const HINTS: [&str; 5] = ["SECRET", "TOKEN", "PASSWORD", "CREDENTIAL", "KEY"];
+    HINTS.iter().any(|hint| upper.contains(hint))
+}
+
+/// Mask an environment value whose name looks like a secret, so the resolved
+/// launch spec can be logged without leaking credentials.
+fn redact_env_value(key: &str, value: &str) -> String {
+    if looks_like_secret(key) {
+        "<redacted>".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Mask argument values that follow a secret-looking flag (both `--token VALUE`
+/// and `--token=VALUE` forms), so credentials passed on the command line do not
+/// land in the launch-spec log in cleartext.
+fn redact_args(args: &[String]) -> Vec<String> {
+    let mut out = Vec::with_capacity(args.len());
+    let mut mask_next = false;
+    for arg in args {
+        if mask_next {
+            out.push("<redacted>".to_string());
+            mask_next = false;
+            continue;
+        }
+        if let Some((flag, _)) = arg.split_once('=') {
+            if looks_like_secret(flag) {
+                out.push(format!("{flag}=<redacted>"));
+                continue;
+            }
+        }
+        if looks_like_secret(arg) {
+            mask_next = true;
+        }
+        out.push(arg.clone());
+    }
+    out
+}
+
 /// Pattern metadata and handle
 pub struct Pattern {
     /// Pattern name
@@ -50,10 +733,41 @@ pub struct Pattern {
     process: Option<Child>,
     /// gRPC endpoint (if running)
     pub grpc_endpoint: Option<String>,
-    /// gRPC client (if connected)
-    client: Option<PatternClient>,
+    /// gRPC port handed to the child by the [`PortAllocator`], tracked so it can
+    /// be released on stop/restart.
+    grpc_port: Option<u16>,
+    /// Data-plane endpoint advertised by the pattern on `start`
+    pub data_endpoint: Option<String>,
+    /// gRPC client (if connected), behind the [`PatternControl`] trait so tests
+    /// can inject a scripted implementor in place of a real connection.
+    client: Option<Box<dyn PatternControl>>,
     /// Pattern configuration
     config: serde_json::Value,
+    /// Resolved launch spec (env, args, working directory) applied on spawn.
+    launch_spec: LaunchSpec,
+    /// Heartbeat and reconnection configuration
+    heartbeat: HeartbeatConfig,
+    /// Shared liveness state, updated by the heartbeat task
+    connection_state: Arc<RwLock<ConnectionState>>,
+    /// Handle to the background heartbeat task (if running)
+    heartbeat_task: Option<JoinHandle<()>>,
+    /// Total number of automatic restarts performed for this pattern.
+    restart_count: u32,
+    /// Timestamps of recent restarts, used to enforce the rolling-window budget.
+    restart_window: VecDeque<Instant>,
+    /// Supervisor restart policy and crash-loop-breaker parameters.
+    restart_config: RestartConfig,
+    /// Set while the manager is intentionally stopping the pattern, so the
+    /// watcher does not mistake a requested exit for a crash.
+    intentional_stop: Arc<AtomicBool>,
+    /// Notified to cancel the per-pattern watcher task (and kill the child).
+    watcher_cancel: Arc<Notify>,
+    /// Handle to the background process watcher (if running).
+    watcher_task: Option<JoinHandle<()>>,
+    /// Consecutive failed health checks observed by the monitor.
+    health_failures: u32,
+    /// Consecutive successful health checks observed by the monitor.
+    health_successes: u32,
 }
 
 impl Pattern {
@@ -66,17 +780,96 @@ impl Pattern {
             status: PatternStatus::Uninitialized,
             process: None,
             grpc_endpoint: None,
+            grpc_port: None,
+            data_endpoint: None,
             client: None,
             config: serde_json::json!({}),
+            launch_spec: LaunchSpec::default(),
+            heartbeat: HeartbeatConfig::default(),
+            connection_state: Arc::new(RwLock::new(ConnectionState::Connected)),
+            heartbeat_task: None,
+            restart_count: 0,
+            restart_window: VecDeque::new(),
+            restart_config: RestartConfig::default(),
+            intentional_stop: Arc::new(AtomicBool::new(false)),
+            watcher_cancel: Arc::new(Notify::new()),
+            watcher_task: None,
+            health_failures: 0,
+            health_successes: 0,
         }
     }
 
-    /// Set pattern configuration
+    /// Fold a health-check outcome into the pattern's consecutive-failure /
+    /// success counters, applying the monitor thresholds to drive
+    /// `Running`→`Degraded`→`Failed` and `Degraded`→`Running` transitions.
+    /// Returns the transition that occurred, if any.
+    fn record_health(&mut self, healthy: bool, config: &MonitorConfig) -> Option<HealthTransition> {
+        if healthy {
+            self.health_failures = 0;
+            self.health_successes = self.health_successes.saturating_add(1);
+            if self.status == PatternStatus::Degraded
+                && self.health_successes >= config.recovery_threshold
+            {
+                self.status = PatternStatus::Running;
+                return Some(HealthTransition::Recovered);
+            }
+            None
+        } else {
+            self.health_successes = 0;
+            self.health_failures = self.health_failures.saturating_add(1);
+            if self.status == PatternStatus::Running
+                && self.health_failures >= config.failure_threshold
+            {
+                self.status = PatternStatus::Degraded;
+                return Some(HealthTransition::Degraded);
+            }
+            if self.status == PatternStatus::Degraded
+                && self.health_failures >= config.failure_threshold.saturating_mul(2)
+            {
+                self.status =
+                    PatternStatus::Failed("health checks failing persistently".to_string());
+                return Some(HealthTransition::Failed);
+            }
+            None
+        }
+    }
+
+    /// Set pattern configuration.
+    ///
+    /// A top-level `"restart_policy"` string (`"always"`, `"on_failure"`, or
+    /// `"never"`) in the config selects the supervisor restart policy, and a
+    /// `"launch"` object supplies the [`LaunchSpec`] applied when the child is
+    /// spawned. A malformed launch spec is dropped here with a warning;
+    /// callers that need it rejected should register via
+    /// [`PatternManager::register_pattern_with_config`], which validates first.
     pub fn with_config(mut self, config: serde_json::Value) -> Self {
+        if let Some(policy) = config.get("restart_policy").and_then(|v| v.as_str()) {
+            self.restart_config.policy = RestartPolicy::from_config(policy);
+        }
+        match LaunchSpec::from_config(&config) {
+            Ok(spec) => self.launch_spec = spec,
+            Err(e) => tracing::warn!(
+                pattern = %self.name,
+                error = %e,
+                "ignoring invalid launch spec in pattern config"
+            ),
+        }
         self.config = config;
         self
     }
 
+    /// Set the heartbeat / reconnection configuration.
+    pub fn with_heartbeat(mut self, heartbeat: HeartbeatConfig) -> Self {
+        self.heartbeat = heartbeat;
+        self
+    }
+
+    /// Set the supervisor restart configuration (policy and crash-loop breaker).
+    pub fn with_restart_config(mut self, restart_config: RestartConfig) -> Self {
+        self.restart_config = restart_config;
+        self
+    }
+
     /// Get pattern status
     pub fn status(&self) -> &PatternStatus {
         &self.status
@@ -101,9 +894,35 @@ impl Pattern {
             "spawning pattern process"
         );
 
-        // Build command with gRPC port argument
+        // Build command with gRPC port argument, then layer on the launch spec:
+        // a cleared environment (if requested) with injected variables, extra
+        // arguments, and a working directory.
         let mut cmd = Command::new(&self.binary_path);
+        if self.launch_spec.clear_env {
+            cmd.env_clear();
+        }
+        cmd.envs(&self.launch_spec.env);
         cmd.arg("--grpc-port").arg(port.to_string());
+        cmd.args(&self.launch_spec.args);
+        if let Some(dir) = &self.launch_spec.working_dir {
+            cmd.current_dir(dir);
+        }
+
+        // Log the resolved launch spec with secret-looking values redacted.
+        let redacted_env: BTreeMap<&str, String> = self
+            .launch_spec
+            .env
+            .iter()
+            .map(|(k, v)| (k.as_str(), redact_env_value(k, v)))
+            .collect();
+        tracing::info!(
+            pattern = %self.name,
+            clear_env = self.launch_spec.clear_env,
+            env = ?redacted_env,
+            extra_args = ?redact_args(&self.launch_spec.args),
+            working_dir = ?self.launch_spec.working_dir,
+            "resolved launch spec"
+        );
 
         // Spawn the process
         let child = cmd.spawn().map_err(|e| {
@@ -119,9 +938,10 @@ impl Pattern {
         let pid = child.id();
         self.process = Some(child);
 
-        // Set gRPC endpoint
+        // Set gRPC endpoint and remember the port so it can be released later.
         let endpoint = format!("http://localhost:{}", port);
         self.grpc_endpoint = Some(endpoint.clone());
+        self.grpc_port = Some(port);
 
         tracing::info!(
             pattern = %self.name,
@@ -167,7 +987,7 @@ impl Pattern {
 
                 match PatternClient::connect(endpoint.clone()).await {
                     Ok(client) => {
-                        self.client = Some(client);
+                        self.client = Some(Box::new(client));
 
                         tracing::info!(
                             pattern = %self.name,
@@ -263,7 +1083,7 @@ impl Pattern {
                 "starting pattern via gRPC"
             );
 
-            client.start().await.map_err(|e| {
+            let data_endpoint = client.start().await.map_err(|e| {
                 tracing::error!(
                     pattern = %self.name,
                     error = %e,
@@ -272,8 +1092,13 @@ impl Pattern {
                 e
             })?;
 
+            if !data_endpoint.is_empty() {
+                self.data_endpoint = Some(data_endpoint.clone());
+            }
+
             tracing::info!(
                 pattern = %self.name,
+                data_endpoint = %data_endpoint,
                 "pattern started successfully"
             );
 
@@ -329,6 +1154,202 @@ impl Pattern {
         Ok(())
     }
 
+    /// Ask the pattern to stop over gRPC without force-killing its process.
+    ///
+    /// Unlike [`Self::stop_pattern`] this never touches the child handle: during
+    /// a coordinated shutdown the watcher owns the child and reaps it when the
+    /// backend exits on its own. Returns whether the backend acknowledged the
+    /// stop within the call.
+    async fn request_stop(&mut self) -> bool {
+        match self.client {
+            Some(ref mut client) => client.stop(30).await.is_ok(),
+            None => false,
+        }
+    }
+
+    /// Spawn the background heartbeat task for this pattern.
+    ///
+    /// The task holds its own gRPC connection (so it never contends with the
+    /// data path) and periodically pings the backend with a zero-payload
+    /// `health_check`, which doubles as a keep-alive so idle connections are not
+    /// reaped. On failure it drives the reconnection state machine using the
+    /// configured [`ReconnectStrategy`], publishing progress through the shared
+    /// [`ConnectionState`] so the router can fast-fail while a pattern is down.
+    fn start_heartbeat(&mut self) {
+        let Some(endpoint) = self.grpc_endpoint.clone() else {
+            return;
+        };
+        let name = self.name.clone();
+        let config = self.heartbeat.clone();
+        let state = self.connection_state.clone();
+
+        let handle = tokio::spawn(async move {
+            let mut attempt: u32 = 0;
+            // Reuse a single client across pings; re-dial on transport failure.
+            let mut client: Option<Box<dyn PatternControl>> = None;
+
+            loop {
+                sleep(config.interval).await;
+
+                // Ensure we have a live client.
+                if client.is_none() {
+                    match PatternClient::connect(endpoint.clone()).await {
+                        Ok(c) => client = Some(Box::new(c)),
+                        Err(e) => {
+                            attempt += 1;
+                            if !note_reconnect(&state, &config, &name, attempt, &e.to_string())
+                                .await
+                            {
+                                return;
+                            }
+                            continue;
+                        }
+                    }
+                }
+
+                let ping = client.as_mut().unwrap().health_check();
+                match tokio::time::timeout(config.timeout, ping).await {
+                    Ok(Ok(_)) => {
+                        attempt = 0;
+                        *state.write().await = ConnectionState::Connected;
+                    }
+                    Ok(Err(e)) => {
+                        attempt += 1;
+                        client = None;
+                        if !note_reconnect(&state, &config, &name, attempt, &e.to_string()).await {
+                            return;
+                        }
+                    }
+                    Err(_) => {
+                        attempt += 1;
+                        client = None;
+                        if !note_reconnect(&state, &config, &name, attempt, "heartbeat timed out")
+                            .await
+                        {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+
+        self.heartbeat_task = Some(handle);
+    }
+
+    /// Stop the background heartbeat task, if running.
+    fn stop_heartbeat(&mut self) {
+        if let Some(handle) = self.heartbeat_task.take() {
+            handle.abort();
+        }
+    }
+
+    /// Record a restart attempt, pruning entries that fell outside the rolling
+    /// window. Returns `true` while the restart budget still permits a restart.
+    fn within_restart_budget(&mut self) -> bool {
+        let window = self.restart_config.window;
+        let max_restarts = self.restart_config.max_restarts;
+        let now = Instant::now();
+        while let Some(front) = self.restart_window.front() {
+            if now.duration_since(*front) > window {
+                self.restart_window.pop_front();
+            } else {
+                break;
+            }
+        }
+        self.restart_window.push_back(now);
+        self.restart_count += 1;
+        self.restart_window.len() as u32 <= max_restarts
+    }
+
+    /// Whether the underlying child process has exited (crashed) without the
+    /// manager asking it to stop. A still-running or absent child is not a crash.
+    fn child_exited(&mut self) -> bool {
+        match self.process.as_mut() {
+            Some(child) => matches!(child.try_wait(), Ok(Some(_))),
+            None => false,
+        }
+    }
+
+    /// Arm the per-pattern watcher: take ownership of the child process and
+    /// spawn a task that `await`s its exit. On an unexpected exit the watcher
+    /// consults the [`RestartPolicy`] and, if a restart is warranted, drives a
+    /// restart through the manager. The watcher shuts down cleanly — killing the
+    /// child — when [`Self::cancel_watcher`] signals it.
+    fn arm_watcher(&mut self, manager: PatternManager) {
+        let Some(mut child) = self.process.take() else {
+            return;
+        };
+        let name = self.name.clone();
+        let policy = self.restart_config.policy;
+        // Fresh per-incarnation handles so signalling this watcher never races a
+        // subsequently armed one.
+        let intentional = Arc::new(AtomicBool::new(false));
+        let cancel = Arc::new(Notify::new());
+        self.intentional_stop = intentional.clone();
+        self.watcher_cancel = cancel.clone();
+
+        let handle = tokio::spawn(async move {
+            let exit = tokio::select! {
+                status = child.wait() => Some(status),
+                _ = cancel.notified() => {
+                    let _ = child.kill().await;
+                    let _ = child.wait().await;
+                    None
+                }
+            };
+            let Some(status) = exit else {
+                return;
+            };
+            if intentional.load(Ordering::SeqCst) {
+                return;
+            }
+
+            let clean = status.map(|s| s.success()).unwrap_or(false);
+            tracing::warn!(pattern = %name, clean_exit = clean, "pattern process exited unexpectedly");
+            if !policy.should_restart(clean) {
+                tracing::info!(pattern = %name, ?policy, "restart policy declines restart");
+                return;
+            }
+            if let Err(e) = manager.restart_pattern(&name).await {
+                tracing::error!(pattern = %name, error = %e, "watcher-driven restart failed");
+            }
+        });
+
+        self.watcher_task = Some(handle);
+    }
+
+    /// Signal the current watcher to stop without waiting for it: mark the stop
+    /// intentional (so the exit is not read as a crash) and wake its cancel
+    /// branch (which kills the child it owns). The [`JoinHandle`] is detached
+    /// rather than awaited, so this is safe to call from inside the watcher task
+    /// itself — as the restart path does.
+    fn signal_watcher_stop(&mut self) {
+        self.intentional_stop.store(true, Ordering::SeqCst);
+        self.watcher_cancel.notify_one();
+        self.watcher_task = None;
+    }
+
+    /// Mark the next watcher-observed exit as intentional without waking the
+    /// cancel branch, so a backend that stops on its own (in response to a gRPC
+    /// stop) is not mistaken for a crash. The watcher keeps running and reaps
+    /// the child via its `child.wait()` branch.
+    fn signal_intentional_stop(&mut self) {
+        self.intentional_stop.store(true, Ordering::SeqCst);
+    }
+
+    /// Stop the per-pattern watcher, marking the stop as intentional so the
+    /// watcher does not treat the resulting exit as a crash, then wait for it to
+    /// kill the child and finish. Must not be called from within the watcher
+    /// task (it would join itself); the restart path uses
+    /// [`Self::signal_watcher_stop`] instead.
+    async fn cancel_watcher(&mut self) {
+        self.intentional_stop.store(true, Ordering::SeqCst);
+        self.watcher_cancel.notify_one();
+        if let Some(handle) = self.watcher_task.take() {
+            let _ = handle.await;
+        }
+    }
+
     /// Health check via gRPC
     async fn health_check_pattern(&mut self) -> crate::Result<PatternStatus> {
         if let Some(ref mut client) = self.client {
@@ -340,19 +1361,43 @@ impl Pattern {
 }
 
 /// Pattern manager - coordinates pattern lifecycle
+#[derive(Clone)]
 pub struct PatternManager {
     /// Registered patterns
     patterns: Arc<RwLock<HashMap<String, Pattern>>>,
+    /// Cancellation tripwire, tripped on coordinated shutdown so the health
+    /// monitor loop stops instead of racing a drain with a restart.
+    shutdown: Tripwire,
+    /// Allocates unique gRPC ports for spawned patterns.
+    ports: PortAllocator,
+    /// Per-pattern endpoint/route map the `Router` resolves against.
+    routes: RouteTable,
 }
 
 impl PatternManager {
-    /// Create a new pattern manager
+    /// Create a new pattern manager, allocating ephemeral gRPC ports.
     pub fn new() -> Self {
+        Self::with_port_allocator(PortAllocator::ephemeral())
+    }
+
+    /// Create a pattern manager with an explicit [`PortAllocator`], e.g. one
+    /// drawing from a configured port range.
+    pub fn with_port_allocator(ports: PortAllocator) -> Self {
         Self {
             patterns: Arc::new(RwLock::new(HashMap::new())),
+            shutdown: Tripwire::new(),
+            ports,
+            routes: RouteTable::new(),
         }
     }
 
+    /// Resolve a pattern name to its currently-active gRPC endpoint, for the
+    /// `Router`. Returns `None` when the pattern is unknown or its route has
+    /// been invalidated (e.g. mid-restart).
+    pub async fn resolve(&self, name: &str) -> Option<Endpoint> {
+        self.routes.resolve(name).await
+    }
+
     /// Register a pattern
     pub async fn register_pattern(&self, name: String, binary_path: PathBuf) -> crate::Result<()> {
         tracing::info!(
@@ -373,12 +1418,64 @@ impl PatternManager {
         Ok(())
     }
 
+    /// Register a pattern with an explicit launch/restart config.
+    ///
+    /// The config's `"launch"` spec is validated up front, so a misconfigured
+    /// environment, argument list, or working directory is rejected here
+    /// rather than surfacing as an opaque spawn failure later.
+    pub async fn register_pattern_with_config(
+        &self,
+        name: String,
+        binary_path: PathBuf,
+        config: serde_json::Value,
+    ) -> crate::Result<()> {
+        tracing::info!(pattern = %name, "registering pattern with config");
+        LaunchSpec::from_config(&config)
+            .map_err(|e| anyhow::anyhow!("invalid launch spec for {name}: {e}"))?;
+        let pattern = Pattern::new(name.clone(), binary_path).with_config(config);
+        self.patterns.write().await.insert(name, pattern);
+        Ok(())
+    }
+
+    /// Register a pattern with an explicit heartbeat / reconnection config.
+    pub async fn register_pattern_with_heartbeat(
+        &self,
+        name: String,
+        binary_path: PathBuf,
+        heartbeat: HeartbeatConfig,
+    ) -> crate::Result<()> {
+        tracing::info!(pattern = %name, "registering pattern with heartbeat config");
+        let pattern = Pattern::new(name.clone(), binary_path).with_heartbeat(heartbeat);
+        self.patterns.write().await.insert(name, pattern);
+        Ok(())
+    }
+
+    /// Current liveness state of the proxy's connection to a pattern.
+    pub async fn connection_state(&self, name: &str) -> Option<ConnectionState> {
+        let patterns = self.patterns.read().await;
+        match patterns.get(name) {
+            Some(pattern) => Some(pattern.connection_state.read().await.clone()),
+            None => None,
+        }
+    }
+
     /// Get pattern by name (returns metadata only, not handles)
-    pub async fn get_pattern(&self, name: &str) -> Option<(String, PatternStatus, Option<String>)> {
+    ///
+    /// The trailing `u32` is the pattern's cumulative restart count, so callers
+    /// can observe supervisor-driven recovery.
+    pub async fn get_pattern(
+        &self,
+        name: &str,
+    ) -> Option<(String, PatternStatus, Option<String>, u32)> {
         let patterns = self.patterns.read().await;
-        patterns
-            .get(name)
-            .map(|p| (p.name.clone(), p.status.clone(), p.grpc_endpoint.clone()))
+        patterns.get(name).map(|p| {
+            (
+                p.name.clone(),
+                p.status.clone(),
+                p.grpc_endpoint.clone(),
+                p.restart_count,
+            )
+        })
     }
 
     /// List all registered patterns
@@ -396,8 +1493,15 @@ impl PatternManager {
             pattern.status = PatternStatus::Starting;
             tracing::info!(pattern = %name, "pattern status: Starting");
 
-            // Allocate a port (for now, use a simple scheme: 9000 + hash)
-            let port = 9000 + (name.chars().map(|c| c as u16).sum::<u16>() % 1000);
+            // Allocate a unique, free gRPC port for the child.
+            let port = match self.ports.allocate().await {
+                Ok(port) => port,
+                Err(e) => {
+                    pattern.status = PatternStatus::Failed(format!("port allocation failed: {e}"));
+                    tracing::error!(pattern = %name, error = %e, "failed to allocate gRPC port");
+                    anyhow::bail!("Failed to allocate port for {name}: {e}");
+                }
+            };
             tracing::info!(pattern = %name, port = port, "allocated gRPC port");
 
             // Spawn the process
@@ -405,6 +1509,7 @@ impl PatternManager {
             if let Err(e) = pattern.spawn(port).await {
                 pattern.status = PatternStatus::Failed(format!("Spawn failed: {}", e));
                 tracing::error!(pattern = %name, error = %e, "failed to spawn pattern");
+                self.ports.release(port).await;
                 anyhow::bail!("Failed to spawn pattern: {}", e);
             }
 
@@ -413,6 +1518,8 @@ impl PatternManager {
             if let Err(e) = pattern.connect_client().await {
                 pattern.status = PatternStatus::Failed(format!("gRPC connect failed: {}", e));
                 tracing::error!(pattern = %name, error = %e, "failed to connect gRPC client");
+                pattern.grpc_port = None;
+                self.ports.release(port).await;
                 anyhow::bail!("Failed to connect gRPC client: {}", e);
             }
 
@@ -421,6 +1528,8 @@ impl PatternManager {
             if let Err(e) = pattern.initialize_pattern().await {
                 pattern.status = PatternStatus::Failed(format!("Initialize failed: {}", e));
                 tracing::error!(pattern = %name, error = %e, "failed to initialize pattern");
+                pattern.grpc_port = None;
+                self.ports.release(port).await;
                 anyhow::bail!("Failed to initialize pattern: {}", e);
             }
 
@@ -429,10 +1538,24 @@ impl PatternManager {
             if let Err(e) = pattern.start_pattern().await {
                 pattern.status = PatternStatus::Failed(format!("Start failed: {}", e));
                 tracing::error!(pattern = %name, error = %e, "failed to start pattern");
+                pattern.grpc_port = None;
+                self.ports.release(port).await;
                 anyhow::bail!("Failed to start pattern: {}", e);
             }
 
             pattern.status = PatternStatus::Running;
+            *pattern.connection_state.write().await = ConnectionState::Connected;
+
+            // Publish the route so the Router can resolve this pattern.
+            if let Some(endpoint) = pattern.grpc_endpoint.clone() {
+                self.routes.publish(name, endpoint, port).await;
+            }
+
+            // Begin liveness monitoring + automatic reconnection.
+            pattern.start_heartbeat();
+            // Watch the child process so an unexpected exit triggers a restart.
+            pattern.arm_watcher(self.clone());
+
             tracing::info!(
                 pattern = %name,
                 endpoint = ?pattern.grpc_endpoint,
@@ -455,11 +1578,23 @@ impl PatternManager {
             pattern.status = PatternStatus::Stopping;
             tracing::info!(pattern = %name, "pattern status: Stopping");
 
+            // Stop liveness monitoring before tearing the process down.
+            pattern.stop_heartbeat();
+            // Cancel the watcher (marking the stop intentional) so it does not
+            // race us into a restart, and let it kill the child it owns.
+            pattern.cancel_watcher().await;
+
             // Send shutdown via gRPC and kill process
             if let Err(e) = pattern.stop_pattern().await {
                 tracing::warn!(pattern = %name, error = %e, "error stopping pattern");
             }
 
+            // Drop the route and return the port to the pool.
+            self.routes.remove(name).await;
+            if let Some(port) = pattern.grpc_port.take() {
+                self.ports.release(port).await;
+            }
+
             pattern.status = PatternStatus::Stopped;
             tracing::info!(pattern = %name, "pattern stopped successfully");
 
@@ -470,6 +1605,381 @@ impl PatternManager {
         }
     }
 
+    /// Drain all running patterns, giving each the same timeout and reason.
+    pub async fn drain_all_patterns(
+        &self,
+        timeout_seconds: i32,
+        reason: String,
+    ) -> crate::Result<()> {
+        let names: Vec<String> = self.list_patterns().await;
+        for name in names {
+            let mut patterns = self.patterns.write().await;
+            if let Some(pattern) = patterns.get_mut(&name) {
+                if !pattern.is_running() {
+                    continue;
+                }
+                if let Some(ref mut client) = pattern.client {
+                    if let Err(e) = client.drain(timeout_seconds, reason.clone()).await {
+                        tracing::warn!(pattern = %name, error = %e, "failed to drain pattern");
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Stop all registered patterns.
+    pub async fn stop_all_patterns(&self) -> crate::Result<()> {
+        let names: Vec<String> = self.list_patterns().await;
+        for name in names {
+            if let Err(e) = self.stop_pattern(&name).await {
+                tracing::warn!(pattern = %name, error = %e, "failed to stop pattern");
+            }
+        }
+        Ok(())
+    }
+
+    /// Coordinated graceful shutdown of every registered pattern.
+    ///
+    /// Trips the shutdown tripwire first, so the health monitor stops instead
+    /// of racing the drain with a restart, then stops each running pattern
+    /// under the supplied timing policy. A pattern gets
+    /// [`ShutdownConfig::grace_period`] to acknowledge a gRPC stop and exit on
+    /// its own; if it does not, its watcher is cancelled — force-killing the
+    /// child — within [`ShutdownConfig::force_after`]. The returned outcomes are
+    /// keyed by pattern name so callers can log a teardown report.
+    pub async fn graceful_shutdown(
+        &self,
+        config: ShutdownConfig,
+    ) -> Vec<(String, ShutdownOutcome)> {
+        // Trip once, before touching any pattern, so the monitor loop stops
+        // and cannot restart a backend out from under the drain.
+        self.shutdown.trip();
+
+        // Drain every pattern concurrently so overall teardown is bounded by
+        // the slowest single pattern rather than the sum of all of them.
+        let mut joins = tokio::task::JoinSet::new();
+        for name in self.list_patterns().await {
+            let manager = self.clone();
+            let config = config.clone();
+            joins.spawn(async move {
+                let outcome = manager.shutdown_one(&name, &config).await;
+                tracing::info!(pattern = %name, ?outcome, "pattern shutdown complete");
+                (name, outcome)
+            });
+        }
+        let mut outcomes = Vec::new();
+        while let Some(joined) = joins.join_next().await {
+            if let Ok(pair) = joined {
+                outcomes.push(pair);
+            }
+        }
+        outcomes
+    }
+
+    /// Stop a single pattern as part of [`Self::graceful_shutdown`], returning
+    /// how it went. Assumes the tripwire has already been tripped.
+    async fn shutdown_one(&self, name: &str, config: &ShutdownConfig) -> ShutdownOutcome {
+        // Check the pattern out of the map under a brief lock, flip its status,
+        // then release the lock so the grace/force waits below never freeze the
+        // rest of the manager (or the other concurrent drains).
+        let mut pattern = {
+            let mut patterns = self.patterns.write().await;
+            match patterns.get(name) {
+                Some(pattern) if pattern.is_running() => {}
+                _ => return ShutdownOutcome::Graceful,
+            }
+            let mut pattern = patterns.remove(name).expect("present and running");
+            pattern.status = PatternStatus::Stopping;
+            pattern.stop_heartbeat();
+            // Mark the stop intentional so the watcher treats the coming exit as
+            // a clean stop rather than a crash to restart.
+            pattern.signal_intentional_stop();
+            pattern
+        };
+        // Stop routing to this pattern immediately.
+        self.routes.invalidate(name).await;
+
+        // Ask the backend to stop and wait for its watcher to observe the
+        // process exiting on its own, both within one grace_period. The handle
+        // is borrowed, not taken, so a grace timeout still leaves it in place
+        // for the force path.
+        let deadline = tokio::time::Instant::now() + config.grace_period;
+        let acknowledged = tokio::time::timeout_at(deadline, pattern.request_stop())
+            .await
+            .unwrap_or(false);
+        let drained = match pattern.watcher_task.as_mut() {
+            Some(handle) => tokio::time::timeout_at(deadline, handle).await.is_ok(),
+            None => false,
+        };
+
+        let outcome = if acknowledged && drained {
+            pattern.watcher_task = None;
+            ShutdownOutcome::Graceful
+        } else {
+            // Straggler: force the watcher to kill the child, bounded by
+            // force_after so a wedged process cannot stall teardown indefinitely.
+            let reaped = tokio::time::timeout(config.force_after, pattern.cancel_watcher())
+                .await
+                .is_ok();
+            if reaped {
+                ShutdownOutcome::Forced
+            } else {
+                ShutdownOutcome::TimedOut
+            }
+        };
+
+        pattern.status = PatternStatus::Stopped;
+        self.routes.remove(name).await;
+        if let Some(port) = pattern.grpc_port.take() {
+            self.ports.release(port).await;
+        }
+        // Return the stopped pattern to the map so get_pattern/list still see it.
+        self.patterns.write().await.insert(name.to_string(), pattern);
+        outcome
+    }
+
+    /// Restart a single pattern in place, enforcing the restart budget and a
+    /// per-restart timeout.
+    ///
+    /// The pattern moves through `Restarting` and ends at `Running` on success
+    /// or `Failed` when bring-up errors, times out, or the restart budget is
+    /// exhausted — at which point the supervisor stops retrying it.
+    pub async fn restart_pattern(&self, name: &str) -> crate::Result<()> {
+        // Check the pattern out of the map so the backoff sleep and the
+        // multi-second bring-up run without holding the global write lock —
+        // other patterns keep serving and `get_pattern`/`forward` stay
+        // responsive throughout the restart.
+        let mut pattern = {
+            let mut patterns = self.patterns.write().await;
+            match patterns.remove(name) {
+                Some(pattern) => pattern,
+                None => anyhow::bail!("Pattern not found: {}", name),
+            }
+        };
+        let result = self.restart_checked_out(name, &mut pattern).await;
+        // Always return the pattern to the map, whatever the outcome.
+        self.patterns.write().await.insert(name.to_string(), pattern);
+        result
+    }
+
+    /// Restart a pattern that has already been checked out of the map. Runs the
+    /// backoff and bring-up without any lock held; the caller owns re-inserting
+    /// the pattern afterwards.
+    async fn restart_checked_out(&self, name: &str, pattern: &mut Pattern) -> crate::Result<()> {
+        if !pattern.within_restart_budget() {
+            let reason = format!(
+                "exceeded {} restarts within {}s",
+                pattern.restart_config.max_restarts,
+                pattern.restart_config.window.as_secs()
+            );
+            tracing::error!(
+                pattern = %name,
+                restarts = pattern.restart_count,
+                "restart budget exhausted, latching Failed"
+            );
+            pattern.status = PatternStatus::Failed(reason.clone());
+            anyhow::bail!("restart budget exhausted for {name}: {reason}");
+        }
+
+        let restart = pattern.restart_count;
+        tracing::warn!(pattern = %name, restart, "restarting pattern");
+        pattern.status = PatternStatus::Restarting;
+
+        // Invalidate the route before tearing the old incarnation down, so the
+        // Router fast-fails instead of resolving to the dying endpoint.
+        self.routes.invalidate(name).await;
+
+        // Tear down the previous incarnation before bringing a fresh one up.
+        pattern.stop_heartbeat();
+        // Signal (without joining) the watcher that observed the exit, so it
+        // kills any lingering child and does not itself loop into a restart.
+        // This is safe even when the watcher drives this very call.
+        pattern.signal_watcher_stop();
+        let _ = pattern.stop_pattern().await;
+        // Return the old port now that the previous child is gone.
+        if let Some(old_port) = pattern.grpc_port.take() {
+            self.ports.release(old_port).await;
+        }
+
+        // Back off before bring-up, doubling with each restart in the window.
+        let backoff = pattern
+            .restart_config
+            .backoff_for(pattern.restart_window.len() as u32);
+        if !backoff.is_zero() {
+            tracing::debug!(pattern = %name, backoff_ms = backoff.as_millis(), "restart backoff");
+            sleep(backoff).await;
+        }
+
+        // Re-run bring-up under a per-restart timeout so a wedged process cannot
+        // stall the supervisor indefinitely.
+        let port = match self.ports.allocate().await {
+            Ok(port) => port,
+            Err(e) => {
+                pattern.status = PatternStatus::Failed(format!("port allocation failed: {e}"));
+                self.routes.remove(name).await;
+                anyhow::bail!("restart failed for {name}: {e}");
+            }
+        };
+        let bring_up = async {
+            pattern.spawn(port).await?;
+            pattern.connect_client().await?;
+            pattern.initialize_pattern().await?;
+            pattern.start_pattern().await?;
+            Ok::<(), anyhow::Error>(())
+        };
+
+        match tokio::time::timeout(RESTART_TIMEOUT, bring_up).await {
+            Ok(Ok(())) => {
+                pattern.status = PatternStatus::Running;
+                *pattern.connection_state.write().await = ConnectionState::Connected;
+                // Re-publish the route atomically now that the fresh endpoint is live.
+                if let Some(endpoint) = pattern.grpc_endpoint.clone() {
+                    self.routes.publish(name, endpoint, port).await;
+                }
+                pattern.start_heartbeat();
+                // Re-arm the watcher over the freshly spawned child.
+                pattern.arm_watcher(self.clone());
+                tracing::info!(pattern = %name, restart, "pattern restarted, Running");
+                Ok(())
+            }
+            Ok(Err(e)) => {
+                pattern.status = PatternStatus::Failed(format!("restart failed: {e}"));
+                pattern.grpc_port = None;
+                self.ports.release(port).await;
+                self.routes.remove(name).await;
+                anyhow::bail!("restart failed for {name}: {e}");
+            }
+            Err(_) => {
+                pattern.status =
+                    PatternStatus::Failed(format!("restart timed out after {}s", RESTART_TIMEOUT.as_secs()));
+                pattern.grpc_port = None;
+                self.ports.release(port).await;
+                self.routes.remove(name).await;
+                anyhow::bail!("restart timed out for {name}");
+            }
+        }
+    }
+
+    /// Run one supervision pass, restarting patterns whose connection was given
+    /// up on, or whose child exited while no watcher owned it, provided they are
+    /// still in budget. An armed watcher takes the child, so crashes of watched
+    /// patterns are restarted by the watcher, not detected here.
+    pub async fn supervise_once(&self) {
+        for name in self.list_patterns().await {
+            let needs_restart = {
+                let mut patterns = self.patterns.write().await;
+                match patterns.get_mut(&name) {
+                    Some(pattern) if pattern.is_running() => {
+                        pattern.child_exited()
+                            || *pattern.connection_state.read().await == ConnectionState::Dead
+                    }
+                    _ => false,
+                }
+            };
+            if needs_restart {
+                tracing::warn!(pattern = %name, "supervisor detected failure, attempting restart");
+                if let Err(e) = self.restart_pattern(&name).await {
+                    tracing::error!(pattern = %name, error = %e, "supervisor restart failed");
+                }
+            }
+        }
+    }
+
+    /// Spawn a background supervisor that periodically runs [`Self::supervise_once`],
+    /// automatically restarting the patterns it flags. The returned handle
+    /// can be aborted to stop supervision.
+    pub fn start_supervisor(&self, interval: Duration) -> JoinHandle<()> {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            loop {
+                sleep(interval).await;
+                manager.supervise_once().await;
+            }
+        })
+    }
+
+    /// Start the background health monitor with the default thresholds, polling
+    /// every running pattern on `interval`.
+    pub fn start_monitor(&self, interval: Duration) -> MonitorHandle {
+        self.start_monitor_with_config(MonitorConfig {
+            interval,
+            ..MonitorConfig::default()
+        })
+    }
+
+    /// Start the background health monitor with an explicit [`MonitorConfig`].
+    ///
+    /// The loop periodically polls each running pattern, folds the outcome into
+    /// its failure/success counters, and feeds terminal failures into the
+    /// supervisor via [`Self::restart_pattern`]. The returned [`MonitorHandle`]
+    /// stops the loop when its `shutdown` future is awaited.
+    pub fn start_monitor_with_config(&self, config: MonitorConfig) -> MonitorHandle {
+        let manager = self.clone();
+        let shutdown = Arc::new(Notify::new());
+        let signal = shutdown.clone();
+        let mut tripwire = self.shutdown.subscribe();
+        let task = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = signal.notified() => break,
+                    _ = tripwire.tripped() => break,
+                    _ = sleep(config.interval) => manager.monitor_once(&config).await,
+                }
+            }
+        });
+        MonitorHandle { shutdown, task }
+    }
+
+    /// Run one monitoring sweep over all running patterns.
+    async fn monitor_once(&self, config: &MonitorConfig) {
+        for name in self.list_patterns().await {
+            // Confirm the pattern is running under a brief read lock, then check
+            // it out so the health-probe RPC below runs without holding the
+            // global lock and stalling `forward`/`get_pattern`.
+            let running = {
+                let patterns = self.patterns.read().await;
+                patterns.get(&name).is_some_and(Pattern::is_running)
+            };
+            if !running {
+                continue;
+            }
+            let Some(mut pattern) = self.patterns.write().await.remove(&name) else {
+                continue;
+            };
+            let healthy = matches!(
+                pattern.health_check_pattern().await,
+                Ok(status) if status.is_serving()
+            );
+            let transition = pattern.record_health(healthy, config);
+            self.patterns.write().await.insert(name.clone(), pattern);
+            match transition {
+                Some(HealthTransition::Degraded) => {
+                    tracing::warn!(pattern = %name, "monitor marked pattern Degraded");
+                }
+                Some(HealthTransition::Recovered) => {
+                    tracing::info!(pattern = %name, "monitor observed pattern recovery");
+                }
+                Some(HealthTransition::Failed) => {
+                    tracing::error!(pattern = %name, "monitor parked pattern in Failed, restarting");
+                    if let Err(e) = self.restart_pattern(&name).await {
+                        tracing::error!(pattern = %name, error = %e, "monitor-driven restart failed");
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// Aggregate readiness view: the current status of every registered pattern.
+    pub async fn health_summary(&self) -> HashMap<String, PatternStatus> {
+        let patterns = self.patterns.read().await;
+        patterns
+            .iter()
+            .map(|(name, pattern)| (name.clone(), pattern.status.clone()))
+            .collect()
+    }
+
     /// Health check a pattern
     pub async fn health_check(&self, name: &str) -> crate::Result<PatternStatus> {
         tracing::debug!(pattern = %name, "performing health check");
@@ -511,6 +2021,109 @@ impl PatternManager {
             anyhow::bail!("Pattern not found: {}", name)
         }
     }
+
+    /// Forward an encoded data-plane request to the named pattern and return the
+    /// raw response bytes.
+    ///
+    /// The router calls this once it has resolved a namespace to a pattern. A
+    /// pattern that is missing or not currently serving surfaces as an
+    /// `unavailable` error so the caller can translate it to
+    /// `Status::unavailable`.
+    pub async fn forward(&self, name: &str, request: Vec<u8>) -> crate::Result<Vec<u8>> {
+        let endpoint = {
+            let patterns = self.patterns.read().await;
+            let pattern = patterns
+                .get(name)
+                .ok_or_else(|| anyhow::anyhow!("unavailable: pattern {name} is not registered"))?;
+            if !pattern.status.is_serving() {
+                anyhow::bail!("unavailable: pattern {name} is {:?}", pattern.status);
+            }
+            if *pattern.connection_state.read().await == ConnectionState::Dead {
+                anyhow::bail!("unavailable: pattern {name} connection is dead");
+            }
+            pattern
+                .data_endpoint
+                .clone()
+                .or_else(|| pattern.grpc_endpoint.clone())
+                .ok_or_else(|| anyhow::anyhow!("unavailable: pattern {name} has no endpoint"))?
+        };
+
+        forward_bytes(&endpoint, request)
+            .await
+            .map_err(|e| anyhow::anyhow!("unavailable: forward to {name} failed: {e}"))
+    }
+}
+
+/// gRPC path the proxy uses to funnel encoded data-plane requests to a pattern.
+const DATA_PLANE_METHOD: &str = "/prism.proxy.v1.DataPlane/Execute";
+
+/// Forward opaque request bytes to a pattern's data-plane endpoint and return
+/// the opaque response bytes.
+///
+/// The proxy treats data-plane payloads as already-encoded frames, so a minimal
+/// pass-through codec is used rather than a generated prost message type.
+async fn forward_bytes(endpoint: &str, request: Vec<u8>) -> crate::Result<Vec<u8>> {
+    use tonic::transport::Endpoint;
+
+    let channel = Endpoint::from_shared(endpoint.to_string())?.connect().await?;
+    let mut grpc = tonic::client::Grpc::new(channel);
+    grpc.ready().await?;
+
+    let path = http::uri::PathAndQuery::from_static(DATA_PLANE_METHOD);
+    let response = grpc
+        .unary(tonic::Request::new(request), path, BytesCodec)
+        .await?;
+    Ok(response.into_inner())
+}
+
+/// A codec that passes request/response bodies through as raw bytes.
+#[derive(Default, Clone)]
+struct BytesCodec;
+
+impl tonic::codec::Codec for BytesCodec {
+    type Encode = Vec<u8>;
+    type Decode = Vec<u8>;
+    type Encoder = BytesCodec;
+    type Decoder = BytesCodec;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        BytesCodec
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        BytesCodec
+    }
+}
+
+impl tonic::codec::Encoder for BytesCodec {
+    type Item = Vec<u8>;
+    type Error = tonic::Status;
+
+    fn encode(
+        &mut self,
+        item: Self::Item,
+        dst: &mut tonic::codec::EncodeBuf<'_>,
+    ) -> Result<(), Self::Error> {
+        use bytes::BufMut;
+        dst.put_slice(&item);
+        Ok(())
+    }
+}
+
+impl tonic::codec::Decoder for BytesCodec {
+    type Item = Vec<u8>;
+    type Error = tonic::Status;
+
+    fn decode(
+        &mut self,
+        src: &mut tonic::codec::DecodeBuf<'_>,
+    ) -> Result<Option<Self::Item>, Self::Error> {
+        use bytes::Buf;
+        let len = src.remaining();
+        let mut out = vec![0u8; len];
+        src.copy_to_slice(&mut out);
+        Ok(Some(out))
+    }
 }
 
 impl Default for PatternManager {
@@ -559,10 +2172,11 @@ mod tests {
         let result = manager.get_pattern("memstore").await;
         assert!(result.is_some(), "Should find registered pattern");
 
-        let (name, status, endpoint) = result.unwrap();
+        let (name, status, endpoint, restarts) = result.unwrap();
         assert_eq!(name, "memstore");
         assert_eq!(status, PatternStatus::Uninitialized);
         assert_eq!(endpoint, None);
+        assert_eq!(restarts, 0);
     }
 
     #[tokio::test]
@@ -625,6 +2239,153 @@ mod tests {
         assert!(result.is_err(), "Should fail to spawn non-existent binary");
     }
 
+    #[test]
+    fn test_reconnect_strategy_exponential_backoff_is_capped() {
+        let strategy = ReconnectStrategy::ExponentialBackoff {
+            base: Duration::from_millis(100),
+            factor: 2,
+            max: Duration::from_secs(1),
+            jitter: false,
+        };
+
+        assert_eq!(strategy.delay(0), Duration::from_millis(100));
+        assert_eq!(strategy.delay(1), Duration::from_millis(200));
+        assert_eq!(strategy.delay(2), Duration::from_millis(400));
+        // Capped at max.
+        assert_eq!(strategy.delay(10), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_connection_state_unknown_pattern() {
+        let manager = PatternManager::new();
+        assert!(manager.connection_state("nope").await.is_none());
+    }
+
+    #[test]
+    fn test_restart_budget_latches_after_max() {
+        let mut pattern = Pattern::new("p".to_string(), PathBuf::from("/nonexistent"));
+        for _ in 0..MAX_RESTARTS {
+            assert!(pattern.within_restart_budget(), "restarts within budget allowed");
+        }
+        // One past the budget within the window is rejected.
+        assert!(!pattern.within_restart_budget(), "over-budget restart rejected");
+        assert_eq!(pattern.restart_count, MAX_RESTARTS + 1);
+    }
+
+    #[test]
+    fn test_child_exited_is_false_without_process() {
+        let mut pattern = Pattern::new("p".to_string(), PathBuf::from("/nonexistent"));
+        assert!(!pattern.child_exited(), "a pattern with no process has not crashed");
+    }
+
+    #[tokio::test]
+    async fn test_restart_unknown_pattern_errors() {
+        let manager = PatternManager::new();
+        assert!(manager.restart_pattern("nope").await.is_err());
+    }
+
+    #[test]
+    fn test_restart_backoff_doubles_and_caps() {
+        let config = RestartConfig {
+            base_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+            ..RestartConfig::default()
+        };
+        assert_eq!(config.backoff_for(1), Duration::from_millis(100));
+        assert_eq!(config.backoff_for(2), Duration::from_millis(200));
+        assert_eq!(config.backoff_for(3), Duration::from_millis(400));
+        // Capped at max_backoff.
+        assert_eq!(config.backoff_for(20), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_restart_policy_decides_by_exit() {
+        assert!(RestartPolicy::Always.should_restart(true));
+        assert!(RestartPolicy::Always.should_restart(false));
+        assert!(!RestartPolicy::OnFailure.should_restart(true));
+        assert!(RestartPolicy::OnFailure.should_restart(false));
+        assert!(!RestartPolicy::Never.should_restart(false));
+    }
+
+    #[test]
+    fn test_restart_policy_from_config() {
+        assert_eq!(RestartPolicy::from_config("always"), RestartPolicy::Always);
+        assert_eq!(RestartPolicy::from_config("never"), RestartPolicy::Never);
+        assert_eq!(
+            RestartPolicy::from_config("on_failure"),
+            RestartPolicy::OnFailure
+        );
+        // Unknown spellings fall back to the default.
+        assert_eq!(RestartPolicy::from_config("bogus"), RestartPolicy::OnFailure);
+    }
+
+    #[test]
+    fn test_monitor_thresholds_drive_status_transitions() {
+        let config = MonitorConfig {
+            interval: Duration::from_secs(1),
+            failure_threshold: 3,
+            recovery_threshold: 2,
+        };
+        let mut pattern = Pattern::new("p".to_string(), PathBuf::from("/test"));
+        pattern.status = PatternStatus::Running;
+
+        // Two failures are below the threshold.
+        assert_eq!(pattern.record_health(false, &config), None);
+        assert_eq!(pattern.record_health(false, &config), None);
+        // The third failure marks the pattern Degraded.
+        assert_eq!(
+            pattern.record_health(false, &config),
+            Some(HealthTransition::Degraded)
+        );
+        assert_eq!(pattern.status, PatternStatus::Degraded);
+
+        // One success is below the recovery threshold.
+        assert_eq!(pattern.record_health(true, &config), None);
+        // The second success recovers to Running.
+        assert_eq!(
+            pattern.record_health(true, &config),
+            Some(HealthTransition::Recovered)
+        );
+        assert_eq!(pattern.status, PatternStatus::Running);
+    }
+
+    #[test]
+    fn test_monitor_parks_persistently_failing_pattern() {
+        let config = MonitorConfig::default();
+        let mut pattern = Pattern::new("p".to_string(), PathBuf::from("/test"));
+        pattern.status = PatternStatus::Running;
+
+        // failure_threshold failures -> Degraded, then up to 2x -> Failed.
+        for _ in 0..config.failure_threshold * 2 {
+            pattern.record_health(false, &config);
+        }
+        assert!(matches!(pattern.status, PatternStatus::Failed(_)));
+    }
+
+    #[tokio::test]
+    async fn test_health_summary_reports_all_patterns() {
+        let manager = PatternManager::new();
+        manager
+            .register_pattern("a".to_string(), PathBuf::from("/a"))
+            .await
+            .unwrap();
+        manager
+            .register_pattern("b".to_string(), PathBuf::from("/b"))
+            .await
+            .unwrap();
+
+        let summary = manager.health_summary().await;
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary.get("a"), Some(&PatternStatus::Uninitialized));
+    }
+
+    #[tokio::test]
+    async fn test_config_selects_restart_policy() {
+        let pattern = Pattern::new("p".to_string(), PathBuf::from("/test"))
+            .with_config(serde_json::json!({ "restart_policy": "always" }));
+        assert_eq!(pattern.restart_config.policy, RestartPolicy::Always);
+    }
+
     #[tokio::test]
     async fn test_pattern_with_config() {
         let config = serde_json::json!({
@@ -637,4 +2398,280 @@ mod tests {
 
         assert_eq!(pattern.config, config);
     }
+
+    #[test]
+    fn test_launch_spec_parses_full_config() {
+        let spec = LaunchSpec::from_config(&serde_json::json!({
+            "launch": {
+                "env": { "RUST_LOG": "debug", "API_TOKEN": "abc" },
+                "clear_env": true,
+                "args": ["--verbose", "--flag"],
+                "working_dir": "/srv/backend"
+            }
+        }))
+        .unwrap();
+
+        assert!(spec.clear_env);
+        assert_eq!(spec.env.get("RUST_LOG").map(String::as_str), Some("debug"));
+        assert_eq!(spec.args, vec!["--verbose", "--flag"]);
+        assert_eq!(spec.working_dir, Some(PathBuf::from("/srv/backend")));
+    }
+
+    #[test]
+    fn test_launch_spec_missing_launch_is_empty() {
+        let spec = LaunchSpec::from_config(&serde_json::json!({ "max_keys": 10 })).unwrap();
+        assert!(spec.env.is_empty());
+        assert!(!spec.clear_env);
+        assert!(spec.args.is_empty());
+        assert!(spec.working_dir.is_none());
+    }
+
+    #[test]
+    fn test_launch_spec_rejects_wrong_types() {
+        assert!(LaunchSpec::from_config(&serde_json::json!({ "launch": { "args": "nope" } })).is_err());
+        assert!(LaunchSpec::from_config(&serde_json::json!({
+            "launch": { "env": { "PORT": 8080 } }
+        }))
+        .is_err());
+        assert!(LaunchSpec::from_config(&serde_json::json!({
+            "launch": { "env": { "": "v" } }
+        }))
+        .is_err());
+        assert!(LaunchSpec::from_config(&serde_json::json!({
+            "launch": { "env": { "X": "a\u{0}b" } }
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn test_redact_env_value_masks_secrets() {
+        assert_eq!(redact_env_value("API_TOKEN", "abc"), "<redacted>");
+        assert_eq!(redact_env_value("db_password", "hunter2"), "<redacted>");
+        assert_eq!(redact_env_value("RUST_LOG", "debug"), "debug");
+    }
+
+    #[test]
+    fn test_redact_args_masks_secret_flag_values() {
+        let args = vec![
+            "--verbose".to_string(),
+            "--api-token".to_string(),
+            "s3cret".to_string(),
+            "--password=hunter2".to_string(),
+            "--config".to_string(),
+            "app.toml".to_string(),
+        ];
+        assert_eq!(
+            redact_args(&args),
+            vec![
+                "--verbose",
+                "--api-token",
+                "<redacted>",
+                "--password=<redacted>",
+                "--config",
+                "app.toml",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_register_pattern_with_config_rejects_bad_launch_spec() {
+        let manager = PatternManager::new();
+        let result = manager
+            .register_pattern_with_config(
+                "bad".to_string(),
+                PathBuf::from("/path/to/bad"),
+                serde_json::json!({ "launch": { "clear_env": "yes" } }),
+            )
+            .await;
+        assert!(result.is_err());
+        assert!(manager.get_pattern("bad").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_graceful_shutdown_reports_per_pattern_outcomes() {
+        let manager = PatternManager::new();
+        manager
+            .register_pattern("memstore".to_string(), PathBuf::from("/path/to/memstore"))
+            .await
+            .unwrap();
+        manager
+            .register_pattern("cache".to_string(), PathBuf::from("/path/to/cache"))
+            .await
+            .unwrap();
+
+        // Neither pattern is running, so both drain cleanly with nothing to kill.
+        let outcomes = manager.graceful_shutdown(ShutdownConfig::default()).await;
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes
+            .iter()
+            .all(|(_, outcome)| *outcome == ShutdownOutcome::Graceful));
+    }
+
+    #[tokio::test]
+    async fn test_graceful_shutdown_trips_tripwire() {
+        let manager = PatternManager::new();
+        let mut listener = manager.shutdown.subscribe();
+
+        manager.graceful_shutdown(ShutdownConfig::default()).await;
+
+        // The wire is tripped, so a fresh listener resolves immediately.
+        listener.tripped().await;
+    }
+
+    /// A scripted [`PatternControl`] that stands in for a real gRPC client, so
+    /// the lifecycle methods can be driven without spawning a process.
+    #[derive(Default)]
+    struct MockPatternControl {
+        started: bool,
+        fail_start: Option<String>,
+        health_sequence: VecDeque<PatternStatus>,
+        stop_called: Arc<AtomicBool>,
+    }
+
+    #[tonic::async_trait]
+    impl PatternControl for MockPatternControl {
+        async fn initialize(
+            &mut self,
+            _name: String,
+            _version: String,
+            _config: serde_json::Value,
+        ) -> crate::Result<()> {
+            Ok(())
+        }
+
+        async fn start(&mut self) -> crate::Result<String> {
+            if let Some(reason) = &self.fail_start {
+                anyhow::bail!("start rejected: {reason}");
+            }
+            self.started = true;
+            Ok("127.0.0.1:0".to_string())
+        }
+
+        async fn drain(&mut self, _timeout_seconds: i32, _reason: String) -> crate::Result<()> {
+            Ok(())
+        }
+
+        async fn stop(&mut self, _timeout_seconds: i32) -> crate::Result<()> {
+            self.stop_called.store(true, Ordering::SeqCst);
+            self.started = false;
+            Ok(())
+        }
+
+        async fn health_check(&mut self) -> crate::Result<PatternStatus> {
+            Ok(self
+                .health_sequence
+                .pop_front()
+                .unwrap_or(PatternStatus::Running))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mock_start_advertises_data_endpoint() {
+        let mut pattern = Pattern::new("mock".to_string(), PathBuf::from("/no/binary"));
+        pattern.client = Some(Box::new(MockPatternControl::default()));
+
+        pattern.start_pattern().await.expect("start should succeed");
+        assert_eq!(pattern.data_endpoint.as_deref(), Some("127.0.0.1:0"));
+    }
+
+    #[tokio::test]
+    async fn test_mock_start_rejection_propagates() {
+        let mut pattern = Pattern::new("mock".to_string(), PathBuf::from("/no/binary"));
+        pattern.client = Some(Box::new(MockPatternControl {
+            fail_start: Some("boom".to_string()),
+            ..Default::default()
+        }));
+
+        assert!(pattern.start_pattern().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mock_health_check_reports_degraded() {
+        let mut mock = MockPatternControl::default();
+        mock.health_sequence.push_back(PatternStatus::Degraded);
+        let mut pattern = Pattern::new("mock".to_string(), PathBuf::from("/no/binary"));
+        pattern.client = Some(Box::new(mock));
+
+        let status = pattern
+            .health_check_pattern()
+            .await
+            .expect("health check should succeed");
+        assert_eq!(status, PatternStatus::Degraded);
+    }
+
+    #[tokio::test]
+    async fn test_mock_stop_calls_grpc_stop_before_kill() {
+        let stop_called = Arc::new(AtomicBool::new(false));
+        let mut pattern = Pattern::new("mock".to_string(), PathBuf::from("/no/binary"));
+        pattern.client = Some(Box::new(MockPatternControl {
+            stop_called: stop_called.clone(),
+            ..Default::default()
+        }));
+
+        pattern.stop_pattern().await.expect("stop should succeed");
+        assert!(
+            stop_called.load(Ordering::SeqCst),
+            "stop_pattern must issue the gRPC stop before killing the process"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_port_allocator_hands_out_unique_ports() {
+        let alloc = PortAllocator::ephemeral();
+        let a = alloc.allocate().await.unwrap();
+        let b = alloc.allocate().await.unwrap();
+        let c = alloc.allocate().await.unwrap();
+        assert!(a != b && b != c && a != c, "ports must be unique: {a} {b} {c}");
+    }
+
+    #[tokio::test]
+    async fn test_port_allocator_release_allows_reuse() {
+        // A single-port range is exhausted after one allocation until released.
+        let port = bind_ephemeral_port().unwrap();
+        let alloc = PortAllocator::range(port, port);
+
+        assert_eq!(alloc.allocate().await.unwrap(), port);
+        assert!(alloc.allocate().await.is_err(), "a range of one is exhausted");
+
+        alloc.release(port).await;
+        assert_eq!(alloc.allocate().await.unwrap(), port);
+    }
+
+    #[tokio::test]
+    async fn test_route_table_resolve_reflects_status() {
+        let table = RouteTable::new();
+        assert!(table.resolve("kv").await.is_none());
+
+        table
+            .publish("kv", "http://localhost:9001".to_string(), 9001)
+            .await;
+        let endpoint = table.resolve("kv").await.expect("active route resolves");
+        assert_eq!(endpoint.url, "http://localhost:9001");
+        assert_eq!(endpoint.port, 9001);
+
+        // Invalidation hides the endpoint but keeps the entry.
+        table.invalidate("kv").await;
+        assert!(table.resolve("kv").await.is_none());
+        assert_eq!(table.status("kv").await, Some(RouteStatus::Invalid));
+
+        // Re-publishing reactivates it atomically on a fresh port.
+        table
+            .publish("kv", "http://localhost:9002".to_string(), 9002)
+            .await;
+        assert_eq!(table.resolve("kv").await.unwrap().port, 9002);
+
+        table.remove("kv").await;
+        assert!(table.status("kv").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_manager_resolve_unstarted_pattern_is_none() {
+        let manager = PatternManager::new();
+        manager
+            .register_pattern("kv".to_string(), PathBuf::from("/path/to/kv"))
+            .await
+            .unwrap();
+        // No route is published until the pattern reaches Running.
+        assert!(manager.resolve("kv").await.is_none());
+    }
 }
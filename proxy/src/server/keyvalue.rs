@@ -5,70 +5,101 @@ use crate::proto::interfaces::keyvalue::{
     DeleteRequest, DeleteResponse, ExistsRequest, ExistsResponse, GetRequest, GetResponse,
     SetRequest, SetResponse,
 };
-use crate::router::Router;
+use prism_proxy::server::filter::{
+    apply_request, apply_response, FilterContext, OperationKind, ProxyFilter,
+};
+use crate::router::SharedRouter;
+use prost::Message;
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
 
+/// Namespace used for KeyValue traffic until per-request namespaces are wired
+/// through the client metadata.
+const DEFAULT_NAMESPACE: &str = "default";
+
 /// KeyValue gRPC service implementation
 pub struct KeyValueService {
-    _router: Arc<Router>,
+    router: SharedRouter,
+    /// Ordered middleware chain applied on the way in and out.
+    filters: Vec<Arc<dyn ProxyFilter>>,
 }
 
 impl KeyValueService {
-    pub fn new(router: Arc<Router>) -> Self {
-        Self { _router: router }
+    pub fn new(router: SharedRouter) -> Self {
+        Self {
+            router,
+            filters: Vec::new(),
+        }
+    }
+
+    /// Install an ordered filter chain.
+    pub fn with_filters(mut self, filters: Vec<Arc<dyn ProxyFilter>>) -> Self {
+        self.filters = filters;
+        self
+    }
+
+    /// Resolve the namespace for an incoming request.
+    ///
+    /// KeyValue requests do not yet carry an explicit namespace, so everything
+    /// currently routes through [`DEFAULT_NAMESPACE`].
+    fn namespace(&self) -> &str {
+        DEFAULT_NAMESPACE
+    }
+
+    /// Encode `req`, run the inbound filter chain, route it to the backing
+    /// pattern, run the outbound chain, and decode the response.
+    async fn forward<Req, Resp>(&self, op: OperationKind, req: Req) -> Result<Resp, Status>
+    where
+        Req: Message,
+        Resp: Message + Default,
+    {
+        let ctx = FilterContext::new(self.namespace(), op);
+        let encoded = apply_request(&self.filters, &ctx, req.encode_to_vec()).await?;
+
+        // Snapshot the current router so an in-flight call is unaffected by a
+        // concurrent hot reload that swaps the routing table.
+        let router = self.router.read().await.clone();
+        let bytes = router
+            .route_request(self.namespace(), encoded)
+            .await
+            .map_err(|e| Status::unavailable(e.to_string()))?;
+
+        let bytes = apply_response(&self.filters, &ctx, bytes).await;
+        Resp::decode(bytes.as_slice())
+            .map_err(|e| Status::internal(format!("malformed pattern response: {e}")))
     }
 }
 
 #[tonic::async_trait]
 impl KeyValueBasicInterface for KeyValueService {
     async fn set(&self, request: Request<SetRequest>) -> Result<Response<SetResponse>, Status> {
-        let _req = request.into_inner();
-
-        // TODO: Route request to appropriate pattern
-        // For now, return success
-        Ok(Response::new(SetResponse {
-            success: true,
-            error: String::new(),
-        }))
+        let resp: SetResponse = self.forward(OperationKind::Set, request.into_inner()).await?;
+        Ok(Response::new(resp))
     }
 
     async fn get(&self, request: Request<GetRequest>) -> Result<Response<GetResponse>, Status> {
-        let _req = request.into_inner();
-
-        // TODO: Route request to appropriate pattern
-        // For now, return not found
-        Ok(Response::new(GetResponse {
-            found: false,
-            value: vec![],
-            error: String::new(),
-        }))
+        let resp: GetResponse = self.forward(OperationKind::Get, request.into_inner()).await?;
+        Ok(Response::new(resp))
     }
 
     async fn delete(
         &self,
         request: Request<DeleteRequest>,
     ) -> Result<Response<DeleteResponse>, Status> {
-        let _req = request.into_inner();
-
-        // TODO: Route request to appropriate pattern
-        Ok(Response::new(DeleteResponse {
-            success: true,
-            error: String::new(),
-        }))
+        let resp: DeleteResponse = self
+            .forward(OperationKind::Delete, request.into_inner())
+            .await?;
+        Ok(Response::new(resp))
     }
 
     async fn exists(
         &self,
         request: Request<ExistsRequest>,
     ) -> Result<Response<ExistsResponse>, Status> {
-        let _req = request.into_inner();
-
-        // TODO: Route request to appropriate pattern
-        Ok(Response::new(ExistsResponse {
-            exists: false,
-            error: String::new(),
-        }))
+        let resp: ExistsResponse = self
+            .forward(OperationKind::Exists, request.into_inner())
+            .await?;
+        Ok(Response::new(resp))
     }
 }
 
@@ -76,20 +107,25 @@ impl KeyValueBasicInterface for KeyValueService {
 mod tests {
     use super::*;
     use crate::pattern::PatternManager;
+    use crate::router::Router;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    /// Build a [`SharedRouter`] backed by a fresh pattern manager.
+    fn shared_router() -> SharedRouter {
+        let pattern_manager = Arc::new(PatternManager::new());
+        Arc::new(RwLock::new(Arc::new(Router::new(pattern_manager))))
+    }
 
     #[tokio::test]
     async fn test_keyvalue_service_creation() {
-        let pattern_manager = Arc::new(PatternManager::new());
-        let router = Arc::new(Router::new(pattern_manager));
-        let _service = KeyValueService::new(router);
+        let _service = KeyValueService::new(shared_router());
         // Service created successfully
     }
 
     #[tokio::test]
-    async fn test_set_request() {
-        let pattern_manager = Arc::new(PatternManager::new());
-        let router = Arc::new(Router::new(pattern_manager));
-        let service = KeyValueService::new(router);
+    async fn test_set_request_without_route_is_unavailable() {
+        let service = KeyValueService::new(shared_router());
 
         let request = Request::new(SetRequest {
             key: "test-key".to_string(),
@@ -97,45 +133,36 @@ mod tests {
             tags: None,
         });
 
-        let response = service.set(request).await;
-        assert!(response.is_ok(), "Set request should succeed");
-
-        let set_response = response.unwrap().into_inner();
-        assert!(set_response.success, "Set should be successful");
+        // With no route registered for the default namespace, the service must
+        // surface the routing failure as `Status::unavailable`.
+        let status = service.set(request).await.expect_err("should be unavailable");
+        assert_eq!(status.code(), tonic::Code::Unavailable);
     }
 
     #[tokio::test]
-    async fn test_get_request() {
-        let pattern_manager = Arc::new(PatternManager::new());
-        let router = Arc::new(Router::new(pattern_manager));
-        let service = KeyValueService::new(router);
+    async fn test_get_request_without_route_is_unavailable() {
+        let service = KeyValueService::new(shared_router());
 
         let request = Request::new(GetRequest {
             key: "test-key".to_string(),
         });
 
-        let response = service.get(request).await;
-        assert!(response.is_ok(), "Get request should succeed");
-
-        let get_response = response.unwrap().into_inner();
-        // For now, should return not found
-        assert!(!get_response.found, "Key should not be found");
+        let status = service.get(request).await.expect_err("should be unavailable");
+        assert_eq!(status.code(), tonic::Code::Unavailable);
     }
 
     #[tokio::test]
-    async fn test_delete_request() {
-        let pattern_manager = Arc::new(PatternManager::new());
-        let router = Arc::new(Router::new(pattern_manager));
-        let service = KeyValueService::new(router);
+    async fn test_delete_request_without_route_is_unavailable() {
+        let service = KeyValueService::new(shared_router());
 
         let request = Request::new(DeleteRequest {
             key: "test-key".to_string(),
         });
 
-        let response = service.delete(request).await;
-        assert!(response.is_ok(), "Delete request should succeed");
-
-        let delete_response = response.unwrap().into_inner();
-        assert!(delete_response.success, "Delete should be successful");
+        let status = service
+            .delete(request)
+            .await
+            .expect_err("should be unavailable");
+        assert_eq!(status.code(), tonic::Code::Unavailable);
     }
 }
@@ -28,30 +28,48 @@ impl ProxyServer {
     }
 
     /// Start the server
+    ///
+    /// The listener is bound before the serve task is spawned so bind failures
+    /// surface synchronously, and `start` only returns once the server is
+    /// accepting connections (signalled over a readiness channel) rather than
+    /// after a fixed sleep.
     pub async fn start(&mut self) -> crate::Result<()> {
         let addr: SocketAddr = self.listen_address.parse()?;
         tracing::info!("Starting proxy server on {}", addr);
 
-        // Create KeyValue service
-        let keyvalue_service = KeyValueService::new(self.router.clone());
+        // Bind before spawning so bind errors propagate out of `start`.
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        let incoming =
+            tonic::transport::server::TcpIncoming::from_listener(listener, true, None)
+                .map_err(|e| anyhow::anyhow!("failed to wrap listener: {e}"))?;
+
+        // Create KeyValue service over a hot-swappable router handle.
+        let keyvalue_service =
+            KeyValueService::new(Arc::new(tokio::sync::RwLock::new(self.router.clone())));
 
         // Create shutdown channel
         let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
         self.shutdown_tx = Some(shutdown_tx);
 
+        // Readiness handshake: fires once the serve task is accepting.
+        let (ready_tx, ready_rx) = oneshot::channel::<()>();
+
         // Start gRPC server
         tokio::spawn(async move {
-            Server::builder()
+            let _ = ready_tx.send(());
+            if let Err(e) = Server::builder()
                 .add_service(KeyValueServer::new(keyvalue_service))
-                .serve_with_shutdown(addr, async {
+                .serve_with_incoming_shutdown(incoming, async {
                     shutdown_rx.await.ok();
                 })
                 .await
-                .expect("gRPC server failed");
+            {
+                tracing::error!(error = %e, "gRPC server exited with error");
+            }
         });
 
-        // Give server time to start
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        // Wait for readiness instead of sleeping.
+        let _ = ready_rx.await;
 
         Ok(())
     }
@@ -0,0 +1,133 @@
+//! Coordinated graceful shutdown.
+//!
+//! Shutting a large deployment down one pattern at a time, each with its own
+//! hardcoded stop timeout, degrades to a worst-case `N × timeout` stall. This
+//! module provides the pieces for a bounded teardown: a broadcast [`Tripwire`]
+//! that the health monitor can `await` to learn that shutdown has begun, and a
+//! [`ShutdownConfig`] describing how long to wait for a clean gRPC drain before
+//! forcing stragglers down.
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Timing policy for a coordinated shutdown.
+#[derive(Debug, Clone)]
+pub struct ShutdownConfig {
+    /// How long to wait for a pattern to stop gracefully via gRPC before it is
+    /// considered a straggler.
+    pub grace_period: Duration,
+    /// Additional time allowed for a force-killed process to actually exit
+    /// before the outcome is reported as timed out.
+    pub force_after: Duration,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            grace_period: Duration::from_secs(30),
+            force_after: Duration::from_secs(5),
+        }
+    }
+}
+
+/// A latching cancellation primitive. Cloning shares the same underlying
+/// channel, so a single [`Tripwire::trip`] wakes every outstanding
+/// [`TripwireListener`] — and because the tripped state latches, listeners
+/// subscribed *after* the trip observe it immediately rather than blocking
+/// forever.
+#[derive(Clone)]
+pub struct Tripwire {
+    tx: Arc<watch::Sender<bool>>,
+}
+
+impl Tripwire {
+    /// Create an untripped tripwire.
+    pub fn new() -> Self {
+        let (tx, _) = watch::channel(false);
+        Self { tx: Arc::new(tx) }
+    }
+
+    /// Trip the wire, waking all current and future listeners. Idempotent.
+    pub fn trip(&self) {
+        // A send only fails when there are no receivers, which is harmless here.
+        let _ = self.tx.send(true);
+    }
+
+    /// Obtain a listener that resolves once the wire is tripped.
+    pub fn subscribe(&self) -> TripwireListener {
+        TripwireListener {
+            rx: self.tx.subscribe(),
+        }
+    }
+}
+
+impl Default for Tripwire {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A per-task handle that resolves when its [`Tripwire`] is tripped.
+pub struct TripwireListener {
+    rx: watch::Receiver<bool>,
+}
+
+impl TripwireListener {
+    /// Resolve once the tripwire has been tripped. Returns immediately if the
+    /// wire is already tripped; a closed channel is likewise treated as
+    /// "tripped" so a task never blocks forever on a dead wire.
+    pub async fn tripped(&mut self) {
+        let _ = self.rx.wait_for(|tripped| *tripped).await;
+    }
+}
+
+/// The fate of a single pattern during a coordinated shutdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownOutcome {
+    /// The pattern acknowledged the gRPC stop within the grace period.
+    Graceful,
+    /// The grace period elapsed, so the process was force-killed.
+    Forced,
+    /// The process did not exit even after being force-killed.
+    TimedOut,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_tripwire_wakes_listeners() {
+        let wire = Tripwire::new();
+        let mut listener = wire.subscribe();
+        wire.trip();
+        // Already tripped: resolves immediately.
+        listener.tripped().await;
+    }
+
+    #[tokio::test]
+    async fn test_tripwire_wakes_listener_subscribed_before_trip() {
+        let wire = Tripwire::new();
+        let mut listener = wire.subscribe();
+        let waiter = tokio::spawn(async move { listener.tripped().await });
+        wire.trip();
+        waiter.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_tripwire_latches_for_late_subscribers() {
+        let wire = Tripwire::new();
+        wire.trip();
+        // Subscribing after the trip still observes it.
+        let mut listener = wire.subscribe();
+        listener.tripped().await;
+    }
+
+    #[test]
+    fn test_shutdown_config_defaults() {
+        let config = ShutdownConfig::default();
+        assert_eq!(config.grace_period, Duration::from_secs(30));
+        assert_eq!(config.force_after, Duration::from_secs(5));
+    }
+}
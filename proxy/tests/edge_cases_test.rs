@@ -331,10 +331,11 @@ async fn test_get_pattern_returns_correct_metadata() {
     let result = manager.get_pattern("metadata-test").await;
     assert!(result.is_some());
 
-    let (name, status, endpoint) = result.unwrap();
+    let (name, status, endpoint, restarts) = result.unwrap();
     assert_eq!(name, "metadata-test");
     assert_eq!(status, PatternStatus::Uninitialized);
     assert_eq!(endpoint, None);
+    assert_eq!(restarts, 0);
 }
 
 #[tokio::test]
@@ -115,14 +115,31 @@ async fn test_server_startup_and_shutdown() {
     let router = Arc::new(Router::new(pattern_manager));
     let mut server = ProxyServer::new(router, "127.0.0.1:18981".to_string());
 
-    // Start server
+    // Start server — returns only once it is actually accepting connections.
     server.start().await.expect("Failed to start server");
     println!("✓ Server started on 127.0.0.1:18981");
 
-    // Give it a moment
-    sleep(Duration::from_millis(100)).await;
-
     // Shutdown server
     server.shutdown().await.expect("Failed to shutdown server");
     println!("✓ Server shut down cleanly");
 }
+
+#[tokio::test]
+async fn test_server_start_fails_on_bind_error() {
+    let pattern_manager = Arc::new(PatternManager::new());
+    let router = Arc::new(Router::new(pattern_manager));
+    let mut server = ProxyServer::new(router, "127.0.0.1:18982".to_string());
+    server.start().await.expect("first bind should succeed");
+
+    // A second server on the same address must fail to bind, and `start` must
+    // surface that error rather than hanging or succeeding.
+    let pattern_manager = Arc::new(PatternManager::new());
+    let router = Arc::new(Router::new(pattern_manager));
+    let mut conflicting = ProxyServer::new(router, "127.0.0.1:18982".to_string());
+    assert!(
+        conflicting.start().await.is_err(),
+        "binding an in-use port should fail"
+    );
+
+    server.shutdown().await.expect("Failed to shutdown server");
+}